@@ -0,0 +1,150 @@
+//! arrow RecordBatch of AnyValue driven by DataType (feature arrow)
+//!
+//! - polars DataType maps to arrow DataType of the same name (Utf8 String as Utf8)
+//! - columns are read by name (strings and Binary borrowed from the batch,
+//!   Large and View variants of Utf8 and Binary read too)
+
+use std::sync::Arc;
+use arrow::array::{Array, ArrayRef, NullArray, BooleanArray, StringArray, BinaryArray};
+use arrow::array::{LargeStringArray, LargeBinaryArray, StringViewArray, BinaryViewArray};
+use arrow::array::{Int64Array, Int32Array, Int16Array, Int8Array};
+use arrow::array::{UInt64Array, UInt32Array, UInt16Array, UInt8Array};
+use arrow::array::{Float64Array, Float32Array};
+use arrow::datatypes::{DataType as ArrowType, Field, Schema, SchemaRef};
+use arrow::error::ArrowError;
+use arrow::record_batch::RecordBatch;
+use crate::polars::prelude::{AnyValue, DataType};
+use crate::is_str;
+
+/// from polars DataType to arrow DataType
+pub fn arrow_dtype(dt: &DataType) -> Result<ArrowType, ArrowError> {
+  Ok(match dt {
+  DataType::Int64 => ArrowType::Int64,
+  DataType::Int32 => ArrowType::Int32,
+  DataType::Int16 => ArrowType::Int16,
+  DataType::Int8 => ArrowType::Int8,
+  DataType::UInt64 => ArrowType::UInt64,
+  DataType::UInt32 => ArrowType::UInt32,
+  DataType::UInt16 => ArrowType::UInt16,
+  DataType::UInt8 => ArrowType::UInt8,
+  DataType::Float64 => ArrowType::Float64,
+  DataType::Float32 => ArrowType::Float32,
+  DataType::Boolean => ArrowType::Boolean,
+  DataType::Binary => ArrowType::Binary,
+  DataType::Null => ArrowType::Null,
+  dt if is_str(dt) => ArrowType::Utf8,
+  dt => return Err(ArrowError::SchemaError(format!("unsupported DataType {:?}", dt)))
+  })
+}
+
+/// arrow DataType t is read as dtype (Large and View variants of Utf8 and Binary)
+pub fn arrow_readable(t: &ArrowType, dt: &DataType) -> Result<bool, ArrowError> {
+  let e = arrow_dtype(dt)?;
+  Ok(*t == e || matches!((&e, t),
+    (ArrowType::Utf8, ArrowType::LargeUtf8 | ArrowType::Utf8View) |
+    (ArrowType::Binary, ArrowType::LargeBinary | ArrowType::BinaryView)))
+}
+
+/// schema of names and dtypes (Null is nullable)
+pub fn arrow_schema(names: &[&str], dtypes: &[DataType]) -> Result<SchemaRef, ArrowError> {
+  let fields = names.iter().zip(dtypes).map(|(n, dt)| arrow_dtype(dt).map(|t|
+    Field::new(*n, t, matches!(dt, DataType::Null)))).collect::<Result<Vec<_>, _>>()?;
+  Ok(Arc::new(Schema::new(fields)))
+}
+
+/// error of value at row and column
+fn value_err(r: usize, n: &str, a: &AnyValue<'_>, dt: &DataType) -> ArrowError {
+  ArrowError::InvalidArgumentError(
+    format!("row {} column {} {:?} is not {:?}", r, n, a, dt))
+}
+
+/// array of column c of rows (typed by dtype)
+fn to_array(rows: &[Vec<AnyValue<'_>>], c: usize, n: &str, dt: &DataType) ->
+  Result<ArrayRef, ArrowError> {
+  /// collect values of variant into typed array
+  macro_rules! col { ($t: ty, $($p: pat => $v: expr),+) => {
+    Arc::new(rows.iter().enumerate().map(|(r, row)| match &row[c] {
+      $($p => Ok($v),)+
+      a => Err(value_err(r, n, a, dt))
+    }).collect::<Result<$t, _>>()?)
+  } }
+  Ok(match dt {
+  DataType::Int64 => col!(Int64Array, AnyValue::Int64(i) => Some(*i)),
+  DataType::Int32 => col!(Int32Array, AnyValue::Int32(i) => Some(*i)),
+  DataType::Int16 => col!(Int16Array, AnyValue::Int16(i) => Some(*i)),
+  DataType::Int8 => col!(Int8Array, AnyValue::Int8(i) => Some(*i)),
+  DataType::UInt64 => col!(UInt64Array, AnyValue::UInt64(u) => Some(*u)),
+  DataType::UInt32 => col!(UInt32Array, AnyValue::UInt32(u) => Some(*u)),
+  DataType::UInt16 => col!(UInt16Array, AnyValue::UInt16(u) => Some(*u)),
+  DataType::UInt8 => col!(UInt8Array, AnyValue::UInt8(u) => Some(*u)),
+  DataType::Float64 => col!(Float64Array, AnyValue::Float64(f) => Some(*f)),
+  DataType::Float32 => col!(Float32Array, AnyValue::Float32(f) => Some(*f)),
+  DataType::Boolean => col!(BooleanArray, AnyValue::Boolean(b) => Some(*b)),
+  DataType::Binary => col!(BinaryArray,
+    AnyValue::Binary(u) => Some(*u), AnyValue::BinaryOwned(u) => Some(&u[..])),
+  DataType::Null => Arc::new(NullArray::new(rows.len())),
+  dt if is_str(dt) => col!(StringArray, crate::__any_str!(s) => Some(*s),
+    crate::__any_str_owned!(s) => Some(s.as_str())),
+  dt => return Err(ArrowError::SchemaError(format!("unsupported DataType {:?}", dt)))
+  })
+}
+
+/// RecordBatch of rows (values checked by dtypes)
+pub fn to_record_batch<'v, I: Iterator<Item = Vec<AnyValue<'v>>>>(names: &[&str],
+  dtypes: &[DataType], rows: I) -> Result<RecordBatch, ArrowError> {
+  let rows = rows.collect::<Vec<_>>();
+  let cols = names.iter().zip(dtypes).enumerate().map(|(c, (n, dt))|
+    to_array(&rows, c, n, dt)).collect::<Result<Vec<_>, _>>()?;
+  RecordBatch::try_new(arrow_schema(names, dtypes)?, cols)
+}
+
+/// value of array at row r (borrowing the array)
+fn from_array<'a>(a: &'a dyn Array, r: usize, dt: &DataType) -> AnyValue<'a> {
+  /// downcast checked by schema
+  fn dc<T: 'static>(a: &dyn Array) -> &T {
+    a.as_any().downcast_ref::<T>().expect("checked by schema")
+  }
+  if a.is_null(r) { return AnyValue::Null; }
+  match dt {
+  DataType::Int64 => AnyValue::Int64(dc::<Int64Array>(a).value(r)),
+  DataType::Int32 => AnyValue::Int32(dc::<Int32Array>(a).value(r)),
+  DataType::Int16 => AnyValue::Int16(dc::<Int16Array>(a).value(r)),
+  DataType::Int8 => AnyValue::Int8(dc::<Int8Array>(a).value(r)),
+  DataType::UInt64 => AnyValue::UInt64(dc::<UInt64Array>(a).value(r)),
+  DataType::UInt32 => AnyValue::UInt32(dc::<UInt32Array>(a).value(r)),
+  DataType::UInt16 => AnyValue::UInt16(dc::<UInt16Array>(a).value(r)),
+  DataType::UInt8 => AnyValue::UInt8(dc::<UInt8Array>(a).value(r)),
+  DataType::Float64 => AnyValue::Float64(dc::<Float64Array>(a).value(r)),
+  DataType::Float32 => AnyValue::Float32(dc::<Float32Array>(a).value(r)),
+  DataType::Boolean => AnyValue::Boolean(dc::<BooleanArray>(a).value(r)),
+  DataType::Binary => AnyValue::Binary(match a.data_type() {
+    ArrowType::LargeBinary => dc::<LargeBinaryArray>(a).value(r),
+    ArrowType::BinaryView => dc::<BinaryViewArray>(a).value(r),
+    _ => dc::<BinaryArray>(a).value(r)
+  }),
+  _ => crate::__any_str!(match a.data_type() {
+    ArrowType::LargeUtf8 => dc::<LargeStringArray>(a).value(r),
+    ArrowType::Utf8View => dc::<StringViewArray>(a).value(r),
+    _ => dc::<StringArray>(a).value(r)
+  })
+  }
+}
+
+/// rows of values from RecordBatch (columns by name, checked by dtypes)
+pub fn from_record_batch<'a>(b: &'a RecordBatch, names: &[&str], dtypes: &[DataType]) ->
+  Result<Vec<Vec<AnyValue<'a>>>, ArrowError> {
+  let cols = names.iter().zip(dtypes).map(|(n, dt)| {
+    let c = b.column_by_name(n).ok_or_else(||
+      ArrowError::SchemaError(format!("no column {}", n)))?;
+    if !arrow_readable(c.data_type(), dt)? {
+      return Err(ArrowError::SchemaError(
+        format!("column {} is {:?} not {:?}", n, c.data_type(), arrow_dtype(dt)?)));
+    }
+    if !matches!(dt, DataType::Null) && c.null_count() > 0 {
+      return Err(ArrowError::InvalidArgumentError(format!("column {} has null", n)));
+    }
+    Ok(c.as_ref())
+  }).collect::<Result<Vec<_>, _>>()?;
+  Ok((0..b.num_rows()).map(|r| cols.iter().zip(dtypes).map(|(c, dt)|
+    from_array(*c, r, dt)).collect()).collect())
+}
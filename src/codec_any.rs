@@ -0,0 +1,167 @@
+//! compact binary row of AnyValue driven by DataType (feature codec)
+//!
+//! - header is the SCHEMA_HASH (u64 little-endian) of the record type
+//! - numerics are little-endian fixed width, Boolean is 1 byte
+//! - strings and Binary are u32 length prefixed (borrowed when decoded)
+
+use std::fmt;
+use crate::polars::prelude::{AnyValue, DataType};
+use crate::is_str;
+
+/// error of codec with byte position
+#[derive(Debug, Clone, PartialEq)]
+pub struct CodecError {
+  /// byte position
+  pub pos: usize,
+  /// message
+  pub msg: String
+}
+
+impl CodecError {
+  /// constructor
+  pub fn new(pos: usize, msg: impl fmt::Display) -> Self {
+    CodecError{pos, msg: msg.to_string()}
+  }
+}
+
+impl fmt::Display for CodecError {
+  /// fmt
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "codec at {}: {}", self.pos, self.msg)
+  }
+}
+
+impl std::error::Error for CodecError {}
+
+/// append header and values checked by dtypes to out (out unchanged on error)
+pub fn encode(v: &[AnyValue<'_>], dtypes: &[DataType], fp: u64, out: &mut Vec<u8>) ->
+  Result<(), CodecError> {
+  /// length prefixed bytes
+  fn bytes(out: &mut Vec<u8>, u: &[u8]) -> Result<(), CodecError> {
+    let n = u32::try_from(u.len()).map_err(|e| CodecError::new(out.len(), e))?;
+    out.extend_from_slice(&n.to_le_bytes());
+    out.extend_from_slice(u);
+    Ok(())
+  }
+  /// values after header
+  fn values(v: &[AnyValue<'_>], dtypes: &[DataType], out: &mut Vec<u8>) ->
+    Result<(), CodecError> {
+    if v.len() != dtypes.len() {
+      return Err(CodecError::new(out.len(),
+        format!("{} values are not {} columns", v.len(), dtypes.len())));
+    }
+    for (a, dt) in v.iter().zip(dtypes) {
+      match (dt, a) {
+      (DataType::Int64, AnyValue::Int64(i)) => out.extend_from_slice(&i.to_le_bytes()),
+      (DataType::Int32, AnyValue::Int32(i)) => out.extend_from_slice(&i.to_le_bytes()),
+      (DataType::Int16, AnyValue::Int16(i)) => out.extend_from_slice(&i.to_le_bytes()),
+      (DataType::Int8, AnyValue::Int8(i)) => out.extend_from_slice(&i.to_le_bytes()),
+      (DataType::UInt64, AnyValue::UInt64(u)) => out.extend_from_slice(&u.to_le_bytes()),
+      (DataType::UInt32, AnyValue::UInt32(u)) => out.extend_from_slice(&u.to_le_bytes()),
+      (DataType::UInt16, AnyValue::UInt16(u)) => out.extend_from_slice(&u.to_le_bytes()),
+      (DataType::UInt8, AnyValue::UInt8(u)) => out.push(*u),
+      (DataType::Float64, AnyValue::Float64(f)) => out.extend_from_slice(&f.to_le_bytes()),
+      (DataType::Float32, AnyValue::Float32(f)) => out.extend_from_slice(&f.to_le_bytes()),
+      (DataType::Boolean, AnyValue::Boolean(b)) => out.push(*b as u8),
+      (DataType::Binary, AnyValue::Binary(u)) => bytes(out, u)?,
+      (DataType::Binary, AnyValue::BinaryOwned(u)) => bytes(out, u)?,
+      (DataType::Null, AnyValue::Null) => {},
+      (dt, crate::__any_str!(s)) if is_str(dt) => bytes(out, s.as_bytes())?,
+      (dt, crate::__any_str_owned!(s)) if is_str(dt) => bytes(out, s.as_bytes())?,
+      (dt, a) => return Err(CodecError::new(out.len(), format!("{:?} is not {:?}", a, dt)))
+      }
+    }
+    Ok(())
+  }
+  let start = out.len();
+  out.extend_from_slice(&fp.to_le_bytes());
+  values(v, dtypes, out).inspect_err(|_| out.truncate(start))
+}
+
+/// cursor of bytes being decoded
+struct Cur<'a> {
+  /// all bytes
+  b: &'a [u8],
+  /// position
+  pos: usize
+}
+
+impl<'a> Cur<'a> {
+  /// next n bytes
+  fn take(&mut self, n: usize) -> Result<&'a [u8], CodecError> {
+    let u = self.b.get(self.pos..self.pos + n).ok_or_else(||
+      CodecError::new(self.pos, format!("{} bytes short", n)))?;
+    self.pos += n;
+    Ok(u)
+  }
+
+  /// next N bytes as array
+  fn arr<const N: usize>(&mut self) -> Result<[u8; N], CodecError> {
+    Ok(self.take(N)?.try_into().expect("length checked"))
+  }
+
+  /// next length prefixed bytes
+  fn bytes(&mut self) -> Result<&'a [u8], CodecError> {
+    let n = u32::from_le_bytes(self.arr()?);
+    self.take(n as usize)
+  }
+
+  /// header and values of dtypes
+  fn row(&mut self, fp: u64, dtypes: &[DataType]) ->
+    Result<Vec<AnyValue<'a>>, CodecError> {
+    let (pos, h) = (self.pos, u64::from_le_bytes(self.arr()?));
+    if h != fp {
+      return Err(CodecError::new(pos,
+        format!("SCHEMA_HASH {:016x} is not {:016x}", h, fp)));
+    }
+    dtypes.iter().map(|dt| Ok(match dt {
+    DataType::Int64 => AnyValue::Int64(i64::from_le_bytes(self.arr()?)),
+    DataType::Int32 => AnyValue::Int32(i32::from_le_bytes(self.arr()?)),
+    DataType::Int16 => AnyValue::Int16(i16::from_le_bytes(self.arr()?)),
+    DataType::Int8 => AnyValue::Int8(i8::from_le_bytes(self.arr()?)),
+    DataType::UInt64 => AnyValue::UInt64(u64::from_le_bytes(self.arr()?)),
+    DataType::UInt32 => AnyValue::UInt32(u32::from_le_bytes(self.arr()?)),
+    DataType::UInt16 => AnyValue::UInt16(u16::from_le_bytes(self.arr()?)),
+    DataType::UInt8 => AnyValue::UInt8(u8::from_le_bytes(self.arr()?)),
+    DataType::Float64 => AnyValue::Float64(f64::from_le_bytes(self.arr()?)),
+    DataType::Float32 => AnyValue::Float32(f32::from_le_bytes(self.arr()?)),
+    DataType::Boolean => match self.arr::<1>()? {
+      [0] => AnyValue::Boolean(false),
+      [1] => AnyValue::Boolean(true),
+      [b] => return Err(CodecError::new(self.pos - 1, format!("{} is not Boolean", b)))
+    },
+    DataType::Binary => AnyValue::Binary(self.bytes()?),
+    DataType::Null => AnyValue::Null,
+    dt if is_str(dt) => {
+      let pos = self.pos;
+      crate::__any_str!(std::str::from_utf8(self.bytes()?)
+        .map_err(|e| CodecError::new(pos, e))?)
+    },
+    dt => return Err(CodecError::new(self.pos, format!("unsupported DataType {:?}", dt)))
+    })).collect()
+  }
+}
+
+/// values of one row (trailing bytes are error, strings borrowed from b)
+pub fn decode<'a>(b: &'a [u8], fp: u64, dtypes: &[DataType]) ->
+  Result<Vec<AnyValue<'a>>, CodecError> {
+  let mut c = Cur{b, pos: 0};
+  let v = c.row(fp, dtypes)?;
+  match c.pos == b.len() {
+  true => Ok(v),
+  false => Err(CodecError::new(c.pos, "trailing bytes"))
+  }
+}
+
+/// values of rows appended one after another (stops at first error)
+pub fn decode_all<'a>(b: &'a [u8], fp: u64, dtypes: &'static [DataType]) ->
+  impl Iterator<Item = Result<Vec<AnyValue<'a>>, CodecError>> + 'a {
+  let mut c = Cur{b, pos: 0};
+  let mut err = false;
+  std::iter::from_fn(move || {
+    if err || c.pos == c.b.len() { return None; }
+    let v = c.row(fp, dtypes);
+    err = v.is_err();
+    Some(v)
+  })
+}
@@ -0,0 +1,180 @@
+//! CSV of AnyValue driven by DataType (feature csv, csv crate)
+//!
+//! - header is names() and columns are mapped by header
+//! - Binary is base64 (read 0x hex too), Boolean is true false (T F 1 0 read)
+//! - read one record at a time (strings borrowed from the record of CsvReader)
+
+use std::fmt;
+use std::io::{Read, Write};
+use base64::Engine;
+use csv::{ReaderBuilder, StringRecord, Terminator, WriterBuilder};
+use crate::polars::prelude::{AnyValue, DataType};
+use crate::is_str;
+
+/// error of CSV with line (1 is header) and column (1 origin, 0 whole record)
+#[derive(Debug, Clone, PartialEq)]
+pub struct CsvError {
+  /// physical line (1 is header, 0 not at a line, first line of multi-line record)
+  pub line: usize,
+  /// column (1 origin, 0 whole record)
+  pub col: usize,
+  /// message
+  pub msg: String
+}
+
+impl CsvError {
+  /// constructor
+  pub fn new(line: usize, col: usize, msg: impl fmt::Display) -> Self {
+    CsvError{line, col, msg: msg.to_string()}
+  }
+}
+
+impl fmt::Display for CsvError {
+  /// fmt
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "csv line {} column {}: {}", self.line, self.col, self.msg)
+  }
+}
+
+impl std::error::Error for CsvError {}
+
+impl From<csv::Error> for CsvError {
+  /// line of the position (column of invalid UTF-8 field)
+  fn from(e: csv::Error) -> Self {
+    let line = e.position().map_or(0, |p| p.line() as usize);
+    let col = match e.kind() {
+    csv::ErrorKind::Utf8{err, ..} => err.field() + 1,
+    _ => 0
+    };
+    CsvError::new(line, col, e)
+  }
+}
+
+/// from AnyValue to field (unquoted)
+fn to_field(a: &AnyValue<'_>) -> Result<String, String> {
+  let b64 = &base64::engine::general_purpose::STANDARD;
+  Ok(match a {
+  AnyValue::Int64(i) => i.to_string(),
+  AnyValue::Int32(i) => i.to_string(),
+  AnyValue::Int16(i) => i.to_string(),
+  AnyValue::Int8(i) => i.to_string(),
+  AnyValue::UInt64(u) => u.to_string(),
+  AnyValue::UInt32(u) => u.to_string(),
+  AnyValue::UInt16(u) => u.to_string(),
+  AnyValue::UInt8(u) => u.to_string(),
+  AnyValue::Float64(f) => f.to_string(),
+  AnyValue::Float32(f) => f.to_string(),
+  AnyValue::Boolean(b) => b.to_string(),
+  crate::__any_str!(s) => s.to_string(),
+  crate::__any_str_owned!(s) => s.to_string(),
+  AnyValue::Binary(u) => b64.encode(u),
+  AnyValue::BinaryOwned(u) => b64.encode(u),
+  AnyValue::Null => String::new(),
+  a => return Err(format!("unsupported AnyValue {:?}", a))
+  })
+}
+
+/// from field to AnyValue of DataType (borrowing string)
+fn from_field<'a>(s: &'a str, dt: &DataType) -> Result<AnyValue<'a>, String> {
+  /// parse number
+  macro_rules! num { ($v: ident) => {
+    s.trim().parse().map(AnyValue::$v).map_err(|e| format!("{:?} {:?}: {}", s, dt, e))
+  } }
+  match dt {
+  DataType::Int64 => num!(Int64),
+  DataType::Int32 => num!(Int32),
+  DataType::Int16 => num!(Int16),
+  DataType::Int8 => num!(Int8),
+  DataType::UInt64 => num!(UInt64),
+  DataType::UInt32 => num!(UInt32),
+  DataType::UInt16 => num!(UInt16),
+  DataType::UInt8 => num!(UInt8),
+  DataType::Float64 => num!(Float64),
+  DataType::Float32 => num!(Float32),
+  DataType::Boolean => match s.trim().to_ascii_lowercase().as_str() {
+    "true" | "t" | "1" => Ok(AnyValue::Boolean(true)),
+    "false" | "f" | "0" => Ok(AnyValue::Boolean(false)),
+    _ => Err(format!("{:?} is not Boolean", s))
+  },
+  DataType::Binary => match s.strip_prefix("0x") {
+    Some(h) if h.len() % 2 == 0 => (0..h.len()).step_by(2).map(|i|
+      u8::from_str_radix(h.get(i..i + 2).unwrap_or("?"), 16)).collect::<Result<_, _>>()
+      .map(AnyValue::BinaryOwned).map_err(|e| format!("{:?} is not hex: {}", s, e)),
+    _ => base64::engine::general_purpose::STANDARD.decode(s)
+      .map(AnyValue::BinaryOwned).map_err(|e| format!("{:?} is not base64: {}", s, e))
+  },
+  DataType::Null => Ok(AnyValue::Null),
+  dt if is_str(dt) => Ok(crate::__any_str!(s)),
+  _ => Err(format!("unsupported DataType {:?}", dt))
+  }
+}
+
+/// write header of names and rows (quoted when needed, lines end with LF)
+pub fn write_csv<'v, W: Write, I: Iterator<Item = Vec<AnyValue<'v>>>>(w: W,
+  names: &[&str], rows: I) -> Result<(), CsvError> {
+  let mut w = WriterBuilder::new().terminator(Terminator::Any(b'\n')).from_writer(w);
+  w.write_record(names).map_err(|e| CsvError::new(1, 0, e))?;
+  let mut line = 2;
+  for v in rows {
+    let fields = v.iter().enumerate().map(|(c, a)|
+      to_field(a).map_err(|m| CsvError::new(line, c + 1, m))).collect::<Result<Vec<_>, _>>()?;
+    w.write_record(&fields).map_err(|e| CsvError::new(line, 0, e))?;
+    line += 1 + fields.iter().map(|f| f.matches('\n').count()).sum::<usize>();
+  }
+  w.flush().map_err(|e| CsvError::new(0, 0, e))
+}
+
+/// reader of one record at a time (header mapped by names at the first read)
+pub struct CsvReader<R> {
+  /// source
+  r: csv::Reader<R>,
+  /// current record
+  rec: StringRecord,
+  /// column of each name in header (None before the first read)
+  pos: Option<Vec<usize>>,
+  /// no more records (end or bad header)
+  end: bool
+}
+
+impl<R: Read> CsvReader<R> {
+  /// constructor (blank lines skipped, records may have any number of fields)
+  pub fn new(r: R) -> Self {
+    let r = ReaderBuilder::new().flexible(true).from_reader(r);
+    CsvReader{r, rec: StringRecord::new(), pos: None, end: false}
+  }
+
+  /// first line of the last record read (1 is header, 0 before reading)
+  pub fn line_no(&self) -> usize {
+    self.rec.position().map_or(0, |p| p.line() as usize)
+  }
+
+  /// column of each name in header
+  fn header(&mut self, names: &[&str]) -> Result<Vec<usize>, CsvError> {
+    let h = self.r.headers()?;
+    if h.is_empty() { return Err(CsvError::new(1, 0, "no header")); }
+    names.iter().map(|n| h.iter().position(|f| f == *n)
+      .ok_or_else(|| CsvError::new(1, 0, format!("no column {}", n)))).collect()
+  }
+
+  /// values of next record (None at end, strings borrowed from the record)
+  pub fn next_values(&mut self, names: &'static [&'static str],
+    dtypes: &'static [DataType]) -> Option<Result<Vec<AnyValue<'_>>, CsvError>> {
+    if self.end { return None; }
+    if self.pos.is_none() {
+      match self.header(names) {
+      Ok(pos) => self.pos = Some(pos),
+      Err(e) => { self.end = true; return Some(Err(e)); }
+      }
+    }
+    match self.r.read_record(&mut self.rec) {
+    Ok(true) => {},
+    Ok(false) => { self.end = true; return None; },
+    Err(e) => return Some(Err(e.into()))
+    }
+    let (line, rec) = (self.line_no(), &self.rec);
+    Some(self.pos.as_ref()?.iter().zip(dtypes).map(|(&p, dt)| {
+      let s = rec.get(p).ok_or_else(|| CsvError::new(line, p + 1, "no field"))?;
+      from_field(s, dt).map_err(|m| CsvError::new(line, p + 1, m))
+    }).collect())
+  }
+}
@@ -1,6 +1,24 @@
 #![doc(html_root_url = "https://docs.rs/iter-tuple/0.3.8")]
 //! Rust iterator for tuple through proc-macro2 struct Vec AnyValue of polars DataFrame
 //!
+//! runtime traits and conversion helpers for generated code
+//! (proc-macro tuple_derive tuple_sqlite3 struct_derive and derive IterTuple
+//! are re-exported)
+//!
+//! # Features
+//!
+//! - polars-0_25 : polars version 0.25.1 (Utf8) (default)
+//! - polars-0_3x : polars version 0.3x (Utf8)
+//! - polars-latest : polars latest (String)
+//! - lazy : polars lazy (option lazy of struct_derive)
+//! - sqlite : sqlite (tuple_sqlite3 and struct_derive, sqlite impls skipped without it) (default)
+//! - serde : serde of StX (map) and RecX (sequence) (Binary as base64)
+//! - csv : read_csv write_csv of StX (header of names)
+//! - arrow : to_record_batch from_record_batch of StX (arrow RecordBatch)
+//! - parquet : read_parquet write_parquet of StX (schema checked)
+//! - ndjson : read_ndjson write_ndjson of StX (one line per row)
+//! - codec : encode decode of StX (compact binary with SCHEMA_HASH header)
+//!
 //! # Sample
 //!
 //! - [https://crates.io/crates/egui-dataframe-sample](https://crates.io/crates/egui-dataframe-sample)
@@ -18,594 +36,928 @@
 //! - [https://crates.io/crates/anyvalue-dataframe](https://crates.io/crates/anyvalue-dataframe)
 //!
 
-use proc_macro::TokenStream;
-use proc_macro2::TokenStream as PM2TS;
-use proc_macro2::{TokenTree, Ident, Literal}; // Group, Punct
-use proc_macro2::{Span}; // Delimiter, Spacing
-use quote::{quote, ToTokens}; // quote::ToTokens in proc_macro2
-use syn; // syn::{parse_macro_input, ItemFn};
-use std::ops::Deref;
-
-/// concat ident to pre ast (before parse as syn::Ident)
-/// - a: true: as is, false: to lowercase
-fn pre_ast_ident(pre: &str, id: &Ident, post: &str, a: bool) -> TokenStream {
-//  let ast_id: syn::Ident = syn::parse_quote! { XXX#id }; // unknown prefix
-//  let id: TokenStream = quote! { XXX#id }.into(); // unknown prefix
-  let mut s = id.to_string();
-  if !a { s = s.to_lowercase(); }
-  let str_id = &format!("{}{}{}", pre, s, post);
-  let mut ts: PM2TS = PM2TS::new(); // proc_macro2::TokenStream
-  Ident::new(str_id, Span::call_site()).to_tokens(&mut ts);
-  ts.into()
+pub use iter_tuple_derive::{tuple_derive, tuple_sqlite3, struct_derive};
+pub use iter_tuple_derive::IterTuple;
+
+#[cfg(not(any(feature = "polars-0_25", feature = "polars-0_3x",
+  feature = "polars-latest")))]
+compile_error!("iter-tuple requires feature polars-0_25 polars-0_3x or polars-latest");
+
+/// polars (selected by feature latest > 0_3x > 0_25)
+#[cfg(feature = "polars-latest")]
+pub use polars_latest as polars;
+/// polars (selected by feature latest > 0_3x > 0_25)
+#[cfg(all(feature = "polars-0_3x", not(feature = "polars-latest")))]
+pub use polars_0_3x as polars;
+/// polars (selected by feature latest > 0_3x > 0_25)
+#[cfg(all(feature = "polars-0_25",
+  not(any(feature = "polars-0_3x", feature = "polars-latest"))))]
+pub use polars_0_25 as polars;
+
+/// sqlite
+#[cfg(feature = "sqlite")]
+pub use sqlite;
+
+/// serde
+#[cfg(feature = "serde")]
+pub use serde;
+
+#[cfg(feature = "serde")]
+pub mod serde_any;
+
+#[cfg(feature = "csv")]
+pub mod csv_any;
+
+/// arrow
+#[cfg(feature = "arrow")]
+pub use arrow;
+
+#[cfg(feature = "arrow")]
+pub mod arrow_any;
+
+/// parquet
+#[cfg(feature = "parquet")]
+pub use parquet;
+
+#[cfg(feature = "parquet")]
+pub mod parquet_any;
+
+#[cfg(feature = "ndjson")]
+pub mod ndjson_any;
+
+#[cfg(feature = "codec")]
+pub mod codec_any;
+
+use polars::prelude::{AnyValue, DataType};
+
+/// trait ToSqlite3ValueVec
+#[cfg(feature = "sqlite")]
+pub trait ToSqlite3ValueVec {
+  /// vec of (:tag, sqlite::Value) to bind
+  fn to_sqlite3_vec(&self) -> Vec<(&'_ str, sqlite::Value)>;
+}
+
+/// trait ToSqlValueVec (V: Value of the backend, sqlite::Value rusqlite Value)
+pub trait ToSqlValueVec<V> {
+  /// vec of (:tag, V) to bind
+  /// (rusqlite named params: map to (tag, &dyn ToSql) borrowed from the vec)
+  fn to_sql_vec(&self) -> Vec<(&'_ str, V)>;
+}
+
+/// trait IntoAnyValueVec
+pub trait IntoAnyValueVec<'a> {
+  /// vec of AnyValue as a row
+  fn into_vec(self) -> Vec<AnyValue<'a>>;
+}
+
+/// trait TupleRecord (implemented by generated RecX and StX)
+pub trait TupleRecord<'a>: Sized {
+  /// type alias of tuple
+  type Tuple;
+  /// number of columns (flattened members count their columns)
+  const LEN: usize;
+  /// FNV-1a 64 of schema_string (computed at expansion time, const with flatten)
+  const SCHEMA_HASH: u64;
+  /// name:DataType of columns in order (name:{...} when flatten)
+  fn schema_string() -> String;
+  /// column names (c0, c1, ... when RecX has no names, name_x when flatten)
+  fn names() -> &'static [&'static str];
+  /// DataTypes of columns
+  fn dtypes() -> &'static [DataType];
+  /// vec of AnyValue borrowing members
+  fn to_anyvalues(&self) -> Vec<AnyValue<'_>>;
+  /// from AnyValue row (panic when shorter than LEN)
+  fn from_anyvalues(v: &'a [AnyValue<'a>]) -> Self;
+}
+
+/// FNV-1a 64 of bytes continued from h (SCHEMA_HASH of flatten)
+#[doc(hidden)]
+pub const fn __schema_hash(mut h: u64, b: &[u8]) -> u64 {
+  let mut i = 0;
+  while i < b.len() { h = (h ^ b[i] as u64).wrapping_mul(0x100000001b3); i += 1; }
+  h
+}
+
+/// error of conversion from AnyValue to RecX and tuple
+#[derive(Debug, Clone, PartialEq)]
+pub enum TupleError {
+  /// number of values not same as LEN (expected, found)
+  Len(usize, usize),
+  /// DataType of value not same as column (index, expected, found)
+  Type(usize, DataType, DataType),
+  /// owned string value (index, the tuple borrows &str from the values)
+  Owned(usize)
+}
+
+impl std::fmt::Display for TupleError {
+  /// fmt
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match self {
+    TupleError::Len(e, n) => write!(f, "expected {} values found {}", e, n),
+    TupleError::Type(i, e, t) =>
+      write!(f, "column {} expected {:?} found {:?}", i, e, t),
+    TupleError::Owned(i) =>
+      write!(f, "column {} is an owned string (tuple borrows &str)", i)
+    }
+  }
 }
 
-/// concat string literal to pre ast (before prepare as Literal)
-/// - a: true: as is, false: to lowercase
-fn pre_ast_string(pre: &str, id: &Ident, post: &str, a: bool) -> TokenStream {
-  let mut s = id.to_string();
-  if !a { s = s.to_lowercase(); }
-  let str_id = &format!("{}{}{}", pre, s, post);
-  let mut ts: PM2TS = PM2TS::new(); // proc_macro2::TokenStream
-  Literal::string(str_id).to_tokens(&mut ts);
-  ts.into()
-}
+impl std::error::Error for TupleError {}
 
-/// usize to pre ast (before parse as Literal)
-fn pre_ast_usize(n: usize) -> TokenStream {
-  let mut ts: PM2TS = PM2TS::new(); // proc_macro2::TokenStream
-  Literal::usize_unsuffixed(n).to_tokens(&mut ts); // #n is usize_suffixed
-  ts.into()
+/// check length and DataType of each value (Null and Unknown accept any)
+/// - owned string (Utf8Owned StringOwned) is Err Owned (can't be borrowed as &str)
+#[allow(clippy::unneeded_struct_pattern)] // Unknown has a field in polars latest
+pub fn check_anyvalues(v: &[AnyValue<'_>], dts: &[DataType]) ->
+  Result<(), TupleError> {
+  if v.len() != dts.len() { return Err(TupleError::Len(dts.len(), v.len())); }
+  for (i, (a, dt)) in v.iter().zip(dts).enumerate() {
+    if matches!(dt, DataType::Null | DataType::Unknown { .. }) { continue; }
+    let t = a.dtype();
+    if t != *dt { return Err(TupleError::Type(i, dt.clone(), t)); }
+    if matches!(a, crate::__any_str_owned!(_)) { return Err(TupleError::Owned(i)); }
+  }
+  Ok(())
+}
+
+/// string DataType of the polars version (Utf8 or String)
+#[cfg(all(any(feature = "serde", feature = "csv", feature = "arrow", feature = "codec"),
+  not(feature = "polars-latest")))]
+pub(crate) fn is_str(dt: &DataType) -> bool { matches!(dt, DataType::Utf8) }
+
+/// string DataType of the polars version (Utf8 or String)
+#[cfg(all(any(feature = "serde", feature = "csv", feature = "arrow", feature = "codec"),
+  feature = "polars-latest"))]
+pub(crate) fn is_str(dt: &DataType) -> bool { matches!(dt, DataType::String) }
+
+/// AnyValue variant of string for the polars version (pattern or expr)
+#[cfg(not(feature = "polars-latest"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __any_str {
+  ($($t: tt)*) => { $crate::polars::prelude::AnyValue::Utf8($($t)*) }
+}
+
+/// AnyValue variant of string for the polars version (pattern or expr)
+#[cfg(feature = "polars-latest")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __any_str {
+  ($($t: tt)*) => { $crate::polars::prelude::AnyValue::String($($t)*) }
+}
+
+/// AnyValue variant of owned string for the polars version (pattern or expr)
+#[cfg(not(feature = "polars-latest"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __any_str_owned {
+  ($($t: tt)*) => { $crate::polars::prelude::AnyValue::Utf8Owned($($t)*) }
+}
+
+/// AnyValue variant of owned string for the polars version (pattern or expr)
+#[cfg(feature = "polars-latest")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __any_str_owned {
+  ($($t: tt)*) => { $crate::polars::prelude::AnyValue::StringOwned($($t)*) }
+}
+
+/// AnyValue Datetime in microseconds without time zone for the polars version (expr)
+#[cfg(not(feature = "polars-latest"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __any_datetime {
+  ($v: expr) => { $crate::polars::prelude::AnyValue::Datetime($v,
+    $crate::polars::prelude::TimeUnit::Microseconds, &None) }
+}
+
+/// AnyValue Datetime in microseconds without time zone for the polars version (expr)
+#[cfg(feature = "polars-latest")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __any_datetime {
+  ($v: expr) => { $crate::polars::prelude::AnyValue::Datetime($v,
+    $crate::polars::prelude::TimeUnit::Microseconds, None) }
+}
+
+/// from anyvalue and datatype to primitive value
+/// - Utf8 and String are the string variant of the polars version
+#[macro_export]
+macro_rules! from_any {
+  ($a: expr, DataType::Int64) => {
+    match $a { $crate::polars::prelude::AnyValue::Int64(i) => i, _ => 0 }
+  };
+  ($a: expr, DataType::Int32) => {
+    match $a { $crate::polars::prelude::AnyValue::Int32(i) => i, _ => 0 }
+  };
+  ($a: expr, DataType::Int16) => {
+    match $a { $crate::polars::prelude::AnyValue::Int16(i) => i, _ => 0 }
+  };
+  ($a: expr, DataType::Int8) => {
+    match $a { $crate::polars::prelude::AnyValue::Int8(i) => i, _ => 0 }
+  };
+  ($a: expr, DataType::UInt64) => {
+    match $a { $crate::polars::prelude::AnyValue::UInt64(u) => u, _ => 0 }
+  };
+  ($a: expr, DataType::UInt32) => {
+    match $a { $crate::polars::prelude::AnyValue::UInt32(u) => u, _ => 0 }
+  };
+  ($a: expr, DataType::UInt16) => {
+    match $a { $crate::polars::prelude::AnyValue::UInt16(u) => u, _ => 0 }
+  };
+  ($a: expr, DataType::UInt8) => {
+    match $a { $crate::polars::prelude::AnyValue::UInt8(u) => u, _ => 0 }
+  };
+  ($a: expr, DataType::Float64) => {
+    match $a { $crate::polars::prelude::AnyValue::Float64(f) => f, _ => 0.0 }
+  };
+  ($a: expr, DataType::Float32) => {
+    match $a { $crate::polars::prelude::AnyValue::Float32(f) => f, _ => 0.0 }
+  };
+  ($a: expr, DataType::Utf8) => { // polars 0.25.1 0.3x
+    match $a { $crate::__any_str!(s) => s, _ => "" }
+  };
+  ($a: expr, DataType::String) => { // polars latest
+    match $a { $crate::__any_str!(s) => s, _ => "" }
+  };
+  ($a: expr, DataType::Boolean) => {
+    match $a { $crate::polars::prelude::AnyValue::Boolean(b) => b, _ => false }
+  };
+  ($a: expr, DataType::BinaryOwned) => { // must match with reference
+    match &$a {
+    $crate::polars::prelude::AnyValue::BinaryOwned(u) => u.clone(),
+    $crate::polars::prelude::AnyValue::Binary(u) => u.to_vec(),
+    _ => vec![]
+    }
+  };
+  ($a: expr, DataType::Binary) => { // must match with reference
+    match &$a {
+    $crate::polars::prelude::AnyValue::Binary(u) => u.to_vec(),
+    $crate::polars::prelude::AnyValue::BinaryOwned(u) => u.clone(),
+    _ => vec![]
+    }
+  };
+  ($a: expr, DataType::Date) => { // days
+    match $a { $crate::polars::prelude::AnyValue::Date(d) => d, _ => 0 }
+  };
+  ($a: expr, DataType::Time) => { // nanoseconds
+    match $a { $crate::polars::prelude::AnyValue::Time(t) => t, _ => 0 }
+  };
+  ($a: expr, DataType::Datetime) => { // microseconds
+    match $a {
+    $crate::polars::prelude::AnyValue::Datetime(t, u, _) => match u {
+      $crate::polars::prelude::TimeUnit::Nanoseconds => t / 1000,
+      $crate::polars::prelude::TimeUnit::Microseconds => t,
+      $crate::polars::prelude::TimeUnit::Milliseconds => t * 1000
+      },
+    _ => 0
+    }
+  };
+  ($a: expr, DataType::Null) => { 0i64 }; // or None must check later
+  ($a: expr, DataType::Unknown) => { 0i64 }; // or None must check later
+  ($a: expr, DataType:: $t: ident) => { 0i64 } // or None must check later
 }
 
-/// from polars DataType to primitive type (proc_macro2::TokenStream)
-fn ast_dtype(dt: &Ident) -> PM2TS {
-  match dt.to_string().as_str() {
-  "Int64" => quote! { i64 },
-  "Int32" => quote! { i32 },
-  "Int16" => quote! { i16 },
-  "Int8" => quote! { i8 },
-  "UInt64" => quote! { u64 },
-  "UInt32" => quote! { u32 },
-  "UInt16" => quote! { u16 },
-  "UInt8" => quote! { u8 },
-  "Float64" => quote! { f64 }, // Decimal in polars latest
-  "Float32" => quote! { f32 }, // Decimal in polars latest
-  "Utf8" => quote! { &'a str }, // polars version 0.25.1
-  "String" => quote! { &'a str }, // polars latest
-  "Boolean" => quote! { bool },
-  "Binary" => quote! { Vec<u8> },
-  "Null" => quote! { i64 }, // must check later
-  "Unknown" => quote! { i64 }, // must check later
-  _ => quote! { i64 } // must check later
-  }
+/// to anyvalue from primitive value and datatype
+/// - let a = to_any!(3, DataType::UInt64);
+/// - let b = to_any!("X", DataType::Utf8);
+#[macro_export]
+macro_rules! to_any {
+  ($v: expr, DataType::Null) => { $crate::polars::prelude::AnyValue::Null };
+  ($v: expr, DataType::Utf8) => { $crate::__any_str!($v) }; // polars 0.25.1 0.3x
+  ($v: expr, DataType::String) => { $crate::__any_str!($v) }; // polars latest
+  ($v: expr, DataType::Datetime) => { $crate::__any_datetime!($v) }; // microseconds
+  ($v: expr, DataType:: $t: ident) => { $crate::polars::prelude::AnyValue::$t($v) }
 }
 
-/// from polars DataType to primitive value (proc_macro2::TokenStream)
-fn ast_dtype_from_anyvalue_col(dt: &Ident, n: &Literal) -> PM2TS {
-/*
-  match dt.to_string().as_str() {
-  "Int64" => quote! { match v[#n] {AnyValue::Int64(i) => i, _ => 0} },
-  "Int32" => quote! { match v[#n] {AnyValue::Int32(i) => i, _ => 0} },
-  "Int16" => quote! { match v[#n] {AnyValue::Int16(i) => i, _ => 0} },
-  "Int8" => quote! { match v[#n] {AnyValue::Int8(i) => i, _ => 0} },
-  "UInt64" => quote! { match v[#n] {AnyValue::UInt64(u) => u, _ => 0} },
-  "UInt32" => quote! { match v[#n] {AnyValue::UInt32(u) => u, _ => 0} },
-  "UInt16" => quote! { match v[#n] {AnyValue::UInt16(u) => u, _ => 0} },
-  "UInt8" => quote! { match v[#n] {AnyValue::UInt8(u) => u, _ => 0} },
-  "Float64" => quote! { match v[#n] {AnyValue::Float64(f) => f, _ => 0.0} },
-  "Float32" => quote! { match v[#n] {AnyValue::Float32(f) => f, _ => 0.0} },
-  "Utf8" => quote! { match v[#n] {
-    AnyValue::Utf8(s) => s, // polars 0.25.1
-    _ => ""
-  } },
-  "String" => quote! { match v[#n] {
-    AnyValue::String(s) => s, // polars latest
-    _ => "".to_string()
-  } },
-  "Boolean" => quote! { match v[#n] {AnyValue::Boolean(b) => b, _ => false} },
-  "Binary" => quote! { match &v[#n] { // must match with reference
-    AnyValue::BinaryOwned(u) => u.clone(),
-    AnyValue::Binary(u) => u.to_vec(),
-    _ => vec![]
-  } },
-  "Null" => quote! { match v[#n] { _ => 0} }, // must check later
-  "Unknown" => quote! { match v[#n] { _ => 0} }, // must check later
-  _ => quote! { match v[#n] { _ => 0} } // must check later
+/// tests
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  /// auto defines StTpl RecTpl and to_tpl
+  #[struct_derive((id, string), (UInt64, Utf8), derive(Debug, Clone, PartialEq))]
+  #[cfg_attr(feature = "sqlite", tuple_sqlite3(UInt64, Utf8))]
+  #[tuple_derive(UInt64, Utf8, derive(Debug, Clone, PartialEq, Eq, Hash, Default),
+    doc = "record of Tpl", attr(must_use))]
+  pub type Tpl<'a> = (u64, &'a str);
+
+  /// [-- --nocapture] [-- --show-output]
+  #[test]
+  fn test_iter_tuple() {
+    assert_eq!(RecTpl::types(), StTpl::types());
+    assert_eq!(StTpl::names(), StTpl::members());
+    assert_eq!(StTpl::dtypes(), StTpl::types());
+    let v = RecTpl::from((3, "a")).v;
+    assert_eq!(v, [to_any!(3, DataType::UInt64), to_any!("a", DataType::Utf8)]);
+    let st = StTpl::from(&v);
+    assert_eq!((from_any!(v[0], DataType::UInt64), st.string), (3, "a"));
+    assert_eq!(st.into_vec(), v);
   }
-*/
-  quote! { from_any!(v[#n], DataType::#dt) }
-}
 
-/// from polars DataType to sqlite3 type WR (proc_macro2::TokenStream)
-fn ast_dtype_to_sqlite3_vec(dt: &Ident, ast_id: &Ident) -> PM2TS {
-  match dt.to_string().as_str() {
-  "Int64" => quote! { self.#ast_id },
-  "Int32" => quote! { (self.#ast_id as i64) },
-  "Int16" => quote! { (self.#ast_id as i64) },
-  "Int8" => quote! { (self.#ast_id as i64) },
-  "UInt64" => quote! { (self.#ast_id as i64) },
-  "UInt32" => quote! { (self.#ast_id as i64) },
-  "UInt16" => quote! { (self.#ast_id as i64) },
-  "UInt8" => quote! { (self.#ast_id as i64) },
-  "Float64" => quote! { self.#ast_id }, // Decimal in polars latest
-  "Float32" => quote! { (self.#ast_id as f64) }, // Decimal in polars latest
-  "Utf8" => quote! { self.#ast_id }, // polars version 0.25.1
-  "String" => quote! { self.#ast_id }, // polars latest
-  "Boolean" => quote! { (if self.#ast_id {"T"} else {"F"}) },
-  "Binary" => quote! { (&self.#ast_id[..]) },
-  "Null" => quote! { self.#ast_id }, // must check later
-  "Unknown" => quote! { self.#ast_id }, // must check later
-  _ => quote! { self.#ast_id } // must check later
+  /// generic over TupleRecord
+  fn round_trip<'a, T: TupleRecord<'a>>(v: &'a [AnyValue<'a>]) -> bool {
+    T::LEN == v.len() && T::from_anyvalues(v).to_anyvalues() == v
   }
-}
 
-/// from polars DataType to sqlite3 type RD (tuple of proc_macro2::TokenStream)
-fn ast_dtype_sqlite3_col(dt: &Ident) -> (PM2TS, PM2TS) {
-  match dt.to_string().as_str() {
-  "Int64" => (quote! { i64 }, quote! {}),
-  "Int32" => (quote! { i64 }, quote! { as i32 }),
-  "Int16" => (quote! { i64 }, quote! { as i16 }),
-  "Int8" => (quote! { i64 }, quote! { as i8 }),
-  "UInt64" => (quote! { i64 }, quote! { as u64 }),
-  "UInt32" => (quote! { i64 }, quote! { as u32 }),
-  "UInt16" => (quote! { i64 }, quote! { as u16 }),
-  "UInt8" => (quote! { i64 }, quote! { as u8 }),
-  "Float64" => (quote! { f64 }, quote! {}), // Decimal in polars latest
-  "Float32" => (quote! { f64 }, quote! { as f32 }), // Decimal in polars latest
-  "Utf8" => (quote! { &'a str }, quote! {}), // polars version 0.25.1
-  "String" => (quote! { &'a str }, quote! {}), // polars latest
-  "Boolean" => (quote! { &'a str }, quote! { == "T" }), // not impl. trait From
-  "Binary" => (quote! { &[u8] }, quote! { .to_vec() }), // not impl. trait From
-  "Null" => (quote! { i64 }, quote! {}), // must check later
-  "Unknown" => (quote! { i64 }, quote! {}), // must check later
-  _ => (quote! { i64 }, quote! {}) // must check later
+  /// [-- --nocapture] [-- --show-output]
+  #[test]
+  fn test_tuple_record() {
+    assert_eq!(RecTpl::names(), StTpl::names());
+    assert_eq!(RecTpl::dtypes(), StTpl::dtypes());
+    let v = RecTpl::from((5, "b")).v;
+    assert!(round_trip::<RecTpl>(&v));
+    assert!(round_trip::<StTpl>(&v));
   }
-}
 
-/// from attr to tuple of sqlite3 cols
-fn sqlite3_cols(attr: PM2TS, n: &mut usize) -> TokenStream {
-  let mut cols = quote! {};
-  for tt in attr { // not use .into_iter().enumerate() to count skip Punct ','
-    match tt {
-    TokenTree::Ident(dt) => { // match only Ident
-//      println!("{}: {:?}", n, dt);
-      let i = pre_ast_usize(*n); // outside of macro call
-      let ast_i = syn::parse_macro_input!(i as Literal);
-      let (t, p) = ast_dtype_sqlite3_col(&dt);
-      cols = quote! {
-        #cols
-        row.read::<#t, _>(#ast_i) #p,
-      };
-      *n += 1;
-    },
-    _ => {} // skip Punct ',' etc
-    }
+  /// [-- --nocapture] [-- --show-output]
+  #[test]
+  fn test_derive_option() {
+    let r = RecTpl::from((1, "x"));
+    let d = RecTpl::default();
+    assert_eq!(d.v, [to_any!(0, DataType::UInt64), to_any!("", DataType::Utf8)]);
+    let set = [r.clone(), d, r.clone()].into_iter()
+      .collect::<std::collections::HashSet<_>>();
+    assert_eq!(set.len(), 2);
+    let o = RecTpl{v: vec![to_any!(1, DataType::UInt64), __any_str_owned!("x".into())]};
+    assert_eq!(o, r); // equal owned and borrowed strings hash the same
+    assert!(set.contains(&o));
+    let st = StTpl::from(&r.v);
+    assert_eq!(st.clone(), st);
+    assert_eq!(format!("{:?}", st), r#"StTpl { id: 1, string: "x" }"#);
   }
-  quote! { (#cols) }.into()
-}
 
-/// from attr to vec of cols type
-fn type_cols(attr: PM2TS) -> TokenStream {
-  let mut cols = quote! {};
-  for tt in attr { // not use .into_iter().enumerate() to count skip Punct ','
-    match tt {
-    TokenTree::Ident(dt) => { // match only Ident
-//      println!("{:?}", dt);
-      cols = quote! {
-        #cols
-        DataType::#dt,
-      };
-    },
-    _ => {} // skip Punct ',' etc
-    }
+  /// names of generated items and visibility of alias
+  #[struct_derive((id, string), (UInt64, Utf8), st = Pair, fn = to_pair)]
+  #[cfg_attr(feature = "sqlite",
+    tuple_sqlite3(UInt64, Utf8, rec = PairRec, fn = pair_from_row))]
+  #[tuple_derive(UInt64, Utf8, rec = PairRec)]
+  pub(crate) type Tpl2<'a> = (u64, &'a str);
+
+  /// [-- --nocapture] [-- --show-output]
+  #[test]
+  fn test_names() {
+    let r = PairRec::from((2, "y"));
+    let p = Pair::from(&r.v);
+    assert_eq!(p.to_pair(), (2, "y"));
+    #[cfg(feature = "sqlite")]
+    let _: fn(&sqlite::Row) -> Tpl2<'_> = pair_from_row;
   }
-  quote! { vec![#cols] }.into()
-}
 
-/// from attr to vec of cols
-fn vec_cols(attr: PM2TS, n: &mut usize) -> TokenStream {
-  let mut cols = quote! {};
-  for tt in attr { // not use .into_iter().enumerate() to count skip Punct ','
-    match tt {
-    TokenTree::Ident(dt) => { // match only Ident
-//      println!("{}: {:?}", n, dt);
-      let i = pre_ast_usize(*n); // outside of macro call
-      let ast_i = syn::parse_macro_input!(i as Literal);
-      let v = match dt.to_string().as_str() {
-      // vec_cols through "Boolean" => ...
-      // "Binary" => quote! { &t.#ast_i }, // use below (can't use .to_owned())
-      "Binary" => quote! { to_any!(t.#ast_i, DataType::BinaryOwned) },
-      // _ => quote! { t.#ast_i } // skip (use below) for support BinaryOwned
-      _ => quote! { to_any!(t.#ast_i, DataType::#dt) }
-      };
-      cols = quote! {
-        #cols
-        // #v.into(), // it is not whole implemented in some version of polars
-        // AnyValue::#dt(#v), // same as below
-        // to_any!(#v, DataType::#dt), // skip (use below) for Binary Owned
-        #v,
-      };
-      *n += 1;
-    },
-    _ => {} // skip Punct ',' etc
-    }
+  /// alias without lifetime
+  #[struct_derive((id, f), (UInt64, Float64))]
+  #[cfg_attr(feature = "sqlite", tuple_sqlite3(UInt64, Float64))]
+  #[tuple_derive(UInt64, Float64)]
+  pub type Num = (u64, f64);
+
+  /// alias with other lifetime and type parameter (generic column through Into)
+  #[struct_derive((id, s, x), (UInt64, Utf8, Int32), derive(Debug, PartialEq))]
+  #[cfg_attr(feature = "sqlite", tuple_sqlite3(UInt64, Utf8, Int32))]
+  #[tuple_derive(UInt64, Utf8, Int32, derive(Debug, Default))]
+  #[allow(type_alias_bounds)]
+  pub type Gen<'b, T: Clone + Into<i32> + From<i32>> = (u64, &'b str, T);
+
+  /// generic column
+  #[derive(Debug, Clone, Copy, PartialEq)]
+  pub struct Score(i32);
+  impl From<i32> for Score { fn from(i: i32) -> Self { Score(i) } }
+  impl From<Score> for i32 { fn from(s: Score) -> Self { s.0 } }
+
+  /// [-- --nocapture] [-- --show-output]
+  #[test]
+  fn test_generics() {
+    let v = RecNum::from((1, 0.5)).v;
+    assert_eq!(StNum::from(&v).to_num(), (1, 0.5));
+    assert!(round_trip::<StNum>(&v));
+    let r = RecGen::<Score>::from((2, "z", Score(7)));
+    assert_eq!(r.v[2], to_any!(7, DataType::Int32));
+    let st = StGen::<Score>::from(&r.v);
+    assert_eq!(st.x, Score(7));
+    assert_eq!(st.to_gen(), (2, "z", Score(7)));
+    assert!(round_trip::<StGen<Score>>(&r.v));
+    assert_eq!(st.into_vec(), r.v);
+    assert_eq!(RecGen::<Score>::default().v.len(), 3);
   }
-  quote! { let v = vec![#cols]; }.into()
-}
 
-/// from attr to sqlite3 vec of member tuple
-fn to_sqlite3_vec(mns: &Vec<Ident>, dts: &Vec<Ident>) -> TokenStream {
-  let mut members = quote! {};
-  for (i, n) in mns.iter().enumerate() {
-    let tag = pre_ast_string(":", n, "", true);
-    let ast_tag = syn::parse_macro_input!(tag as Literal); // be TokenStream
-    let id = pre_ast_ident("", n, "", true);
-    let ast_id = syn::parse_macro_input!(id as syn::Ident); // be TokenStream
-    let v = ast_dtype_to_sqlite3_vec(&dts[i], &ast_id);
-    members = quote! {
-      #members
-      (#ast_tag, #v.into()),
-    }
+  /// [-- --nocapture] [-- --show-output]
+  #[test]
+  fn test_iter_ref() {
+    let t = (4, "d");
+    let mut it = RecTpl::iter_ref(&t);
+    assert_eq!(it.len(), 2);
+    assert_eq!(it.next_back(), Some(to_any!("d", DataType::Utf8)));
+    assert_eq!((it.next(), it.next()), (Some(to_any!(4, DataType::UInt64)), None));
+    assert!(RecTpl::iter_ref(&t).eq(RecTpl::from(t).v));
+    let g = (2, "z", Score(7));
+    assert_eq!(RecGen::iter_ref(&g).rev().collect::<Vec<_>>()[0], to_any!(7, DataType::Int32));
   }
-  quote! { vec![#members] }.into() // be TokenStream
-}
 
-/// from attr to list of value
-fn from_anyvalue_col(dts: &Vec<Ident>) -> TokenStream {
-  let mut members = quote! {};
-  for (i, dt) in dts.iter().enumerate() {
-    let u = pre_ast_usize(i); // outside of macro call
-    let ast_i = syn::parse_macro_input!(u as Literal);
-    let v = ast_dtype_from_anyvalue_col(dt, &ast_i);
-    members = quote! {
-      #members
-      #v,
-    };
+  /// [-- --nocapture] [-- --show-output]
+  #[test]
+  fn test_named() {
+    let mut r = RecTpl::from((6, "e"));
+    assert_eq!((RecTpl::index_of("string"), RecTpl::index_of("x")), (Some(1), None));
+    assert_eq!(r["id"], to_any!(6, DataType::UInt64));
+    r["string"] = to_any!("f", DataType::Utf8);
+    assert_eq!(r.get("string"), Some(&to_any!("f", DataType::Utf8)));
+    assert!(r.get_mut("x").is_none());
+    let g = RecGen::<Score>::from((2, "z", Score(7)));
+    assert_eq!(g.iter_named().map(|(n, _)| n).collect::<Vec<_>>(), ["id", "s", "x"]);
   }
-  quote! { (#members) }.into() // be TokenStream
-}
 
-/// from attr to from_tuple of member
-fn from_tuple_members(mns: &Vec<Ident>) -> TokenStream {
-  let mut members = quote! {};
-  for (i, n) in mns.iter().enumerate() {
-    let id = pre_ast_ident("", n, "", true);
-    let ast_id = syn::parse_macro_input!(id as syn::Ident); // be TokenStream
-    let u = pre_ast_usize(i); // outside of macro call
-    let ast_i = syn::parse_macro_input!(u as Literal);
-    members = quote! {
-      #members
-      #ast_id: t.#ast_i,
-    };
+  /// [-- --nocapture] [-- --show-output]
+  #[test]
+  fn test_into_tuple() {
+    let mut r = RecTpl::from((8, "g"));
+    r["id"] = to_any!(9, DataType::UInt64);
+    assert_eq!(r.clone().into_tuple(), Ok((9, "g")));
+    let v = RecGen::<Score>::from((2, "z", Score(7))).v;
+    let g = RecGen::<Score>::try_from(v).and_then(RecGen::into_tuple);
+    assert_eq!(g.map(|t| t.2), Ok(Score(7)));
+    r["string"] = to_any!(1, DataType::Int32);
+    assert_eq!(Tpl::try_from(r).unwrap_err(),
+      TupleError::Type(1, RecTpl::types()[1].clone(), DataType::Int32));
+    assert_eq!(RecNum::try_from(vec![]).err(), Some(TupleError::Len(2, 0)));
+    let v = vec![to_any!(1, DataType::UInt64), crate::__any_str_owned!("h".into())];
+    assert_eq!(RecTpl::try_from(v).err(), Some(TupleError::Owned(1)));
   }
-  members.into() // be TokenStream
-}
 
-/// from attr to to_tuple of member
-fn to_tuple_members(mns: &Vec<Ident>, dts: &Vec<Ident>) -> TokenStream {
-  let mut members = quote! {};
-  for (i, n) in mns.iter().enumerate() {
-    let id = pre_ast_ident("", n, "", true);
-    let ast_id = syn::parse_macro_input!(id as syn::Ident); // be TokenStream
-    let v = match dts[i].to_string().as_str() {
-    // to_tuple_members through "Boolean" => ...
-    "Binary" => quote! { self.#ast_id.clone() },
-    _ => quote! { self.#ast_id }
-    };
-    members = quote! {
-      #members
-      #v,
-    };
+  /// single element alias
+  #[struct_derive((id,), (UInt64,), derive(Default))]
+  #[cfg_attr(feature = "sqlite", tuple_sqlite3(UInt64))]
+  #[tuple_derive(UInt64)]
+  pub type One = (u64,);
+
+  /// wide alias (120 columns)
+  #[struct_derive((
+    c0, c1, c2, c3, c4, c5, c6, c7, c8, c9, c10, c11,
+    c12, c13, c14, c15, c16, c17, c18, c19, c20, c21, c22, c23,
+    c24, c25, c26, c27, c28, c29, c30, c31, c32, c33, c34, c35,
+    c36, c37, c38, c39, c40, c41, c42, c43, c44, c45, c46, c47,
+    c48, c49, c50, c51, c52, c53, c54, c55, c56, c57, c58, c59,
+    c60, c61, c62, c63, c64, c65, c66, c67, c68, c69, c70, c71,
+    c72, c73, c74, c75, c76, c77, c78, c79, c80, c81, c82, c83,
+    c84, c85, c86, c87, c88, c89, c90, c91, c92, c93, c94, c95,
+    c96, c97, c98, c99, c100, c101, c102, c103, c104, c105, c106, c107,
+    c108, c109, c110, c111, c112, c113, c114, c115, c116, c117, c118, c119), (
+    UInt64, Utf8, Float64, Boolean, Int32, UInt64, Utf8, Float64,
+    Boolean, Int32, UInt64, Utf8, Float64, Boolean, Int32, UInt64,
+    Utf8, Float64, Boolean, Int32, UInt64, Utf8, Float64, Boolean,
+    Int32, UInt64, Utf8, Float64, Boolean, Int32, UInt64, Utf8,
+    Float64, Boolean, Int32, UInt64, Utf8, Float64, Boolean, Int32,
+    UInt64, Utf8, Float64, Boolean, Int32, UInt64, Utf8, Float64,
+    Boolean, Int32, UInt64, Utf8, Float64, Boolean, Int32, UInt64,
+    Utf8, Float64, Boolean, Int32, UInt64, Utf8, Float64, Boolean,
+    Int32, UInt64, Utf8, Float64, Boolean, Int32, UInt64, Utf8,
+    Float64, Boolean, Int32, UInt64, Utf8, Float64, Boolean, Int32,
+    UInt64, Utf8, Float64, Boolean, Int32, UInt64, Utf8, Float64,
+    Boolean, Int32, UInt64, Utf8, Float64, Boolean, Int32, UInt64,
+    Utf8, Float64, Boolean, Int32, UInt64, Utf8, Float64, Boolean,
+    Int32, UInt64, Utf8, Float64, Boolean, Int32, UInt64, Utf8,
+    Float64, Boolean, Int32, UInt64, Utf8, Float64, Boolean, Int32), derive(Default))]
+  #[cfg_attr(feature = "sqlite", tuple_sqlite3(
+    UInt64, Utf8, Float64, Boolean, Int32, UInt64, Utf8, Float64,
+    Boolean, Int32, UInt64, Utf8, Float64, Boolean, Int32, UInt64,
+    Utf8, Float64, Boolean, Int32, UInt64, Utf8, Float64, Boolean,
+    Int32, UInt64, Utf8, Float64, Boolean, Int32, UInt64, Utf8,
+    Float64, Boolean, Int32, UInt64, Utf8, Float64, Boolean, Int32,
+    UInt64, Utf8, Float64, Boolean, Int32, UInt64, Utf8, Float64,
+    Boolean, Int32, UInt64, Utf8, Float64, Boolean, Int32, UInt64,
+    Utf8, Float64, Boolean, Int32, UInt64, Utf8, Float64, Boolean,
+    Int32, UInt64, Utf8, Float64, Boolean, Int32, UInt64, Utf8,
+    Float64, Boolean, Int32, UInt64, Utf8, Float64, Boolean, Int32,
+    UInt64, Utf8, Float64, Boolean, Int32, UInt64, Utf8, Float64,
+    Boolean, Int32, UInt64, Utf8, Float64, Boolean, Int32, UInt64,
+    Utf8, Float64, Boolean, Int32, UInt64, Utf8, Float64, Boolean,
+    Int32, UInt64, Utf8, Float64, Boolean, Int32, UInt64, Utf8,
+    Float64, Boolean, Int32, UInt64, Utf8, Float64, Boolean, Int32))]
+  #[tuple_derive(
+    UInt64, Utf8, Float64, Boolean, Int32, UInt64, Utf8, Float64,
+    Boolean, Int32, UInt64, Utf8, Float64, Boolean, Int32, UInt64,
+    Utf8, Float64, Boolean, Int32, UInt64, Utf8, Float64, Boolean,
+    Int32, UInt64, Utf8, Float64, Boolean, Int32, UInt64, Utf8,
+    Float64, Boolean, Int32, UInt64, Utf8, Float64, Boolean, Int32,
+    UInt64, Utf8, Float64, Boolean, Int32, UInt64, Utf8, Float64,
+    Boolean, Int32, UInt64, Utf8, Float64, Boolean, Int32, UInt64,
+    Utf8, Float64, Boolean, Int32, UInt64, Utf8, Float64, Boolean,
+    Int32, UInt64, Utf8, Float64, Boolean, Int32, UInt64, Utf8,
+    Float64, Boolean, Int32, UInt64, Utf8, Float64, Boolean, Int32,
+    UInt64, Utf8, Float64, Boolean, Int32, UInt64, Utf8, Float64,
+    Boolean, Int32, UInt64, Utf8, Float64, Boolean, Int32, UInt64,
+    Utf8, Float64, Boolean, Int32, UInt64, Utf8, Float64, Boolean,
+    Int32, UInt64, Utf8, Float64, Boolean, Int32, UInt64, Utf8,
+    Float64, Boolean, Int32, UInt64, Utf8, Float64, Boolean, Int32)]
+  pub type Wide<'a> = (
+    u64, &'a str, f64, bool, i32, u64, &'a str, f64,
+    bool, i32, u64, &'a str, f64, bool, i32, u64,
+    &'a str, f64, bool, i32, u64, &'a str, f64, bool,
+    i32, u64, &'a str, f64, bool, i32, u64, &'a str,
+    f64, bool, i32, u64, &'a str, f64, bool, i32,
+    u64, &'a str, f64, bool, i32, u64, &'a str, f64,
+    bool, i32, u64, &'a str, f64, bool, i32, u64,
+    &'a str, f64, bool, i32, u64, &'a str, f64, bool,
+    i32, u64, &'a str, f64, bool, i32, u64, &'a str,
+    f64, bool, i32, u64, &'a str, f64, bool, i32,
+    u64, &'a str, f64, bool, i32, u64, &'a str, f64,
+    bool, i32, u64, &'a str, f64, bool, i32, u64,
+    &'a str, f64, bool, i32, u64, &'a str, f64, bool,
+    i32, u64, &'a str, f64, bool, i32, u64, &'a str,
+    f64, bool, i32, u64, &'a str, f64, bool, i32);
+
+  /// [-- --nocapture] [-- --show-output]
+  #[test]
+  fn test_tuple_width() {
+    let v = RecOne::from((4,)).v;
+    assert_eq!(StOne::from(&v).to_one(), (4,));
+    assert!(round_trip::<RecOne>(&v));
+    let v = RecWide::from(StWide::default().to_wide()).v;
+    assert_eq!((RecWide::LEN, StWide::names()[119]), (120, "c119"));
+    assert!(round_trip::<StWide>(&v));
+    assert!(round_trip::<RecWide>(&v));
   }
-  quote! { (#members) }.into() // be TokenStream
-}
 
-/// from attr to list of DataType
-fn type_members(dts: &Vec<Ident>) -> TokenStream {
-  let mut members = quote! {};
-  for dt in dts.iter() {
-    members = quote! {
-      #members
-      DataType::#dt,
-    };
+  /// derive IterTuple on a named struct
+  #[derive(IterTuple)]
+  pub struct Person<'a> {
+    pub id: u64,
+    #[iter_tuple(dtype = Utf8)]
+    pub name: &'a str
   }
-  quote! { vec![#members] }.into() // be TokenStream
-}
 
-/// from attr to list of member stringify
-fn str_members(mns: &Vec<Ident>) -> TokenStream {
-  let mut members = quote! {};
-  for n in mns.iter() {
-    let id = pre_ast_ident("", n, "", true);
-    let ast_id = syn::parse_macro_input!(id as syn::Ident); // be TokenStream
-    members = quote! {
-      #members
-      stringify!(#ast_id),
-    };
+  /// [-- --nocapture] [-- --show-output]
+  #[test]
+  fn test_derive_iter_tuple() {
+    assert_eq!(Person::names(), ["id", "name"]);
+    assert_eq!(Person::dtypes(), StTpl::dtypes());
+    let v = RecTpl::from((7, "c")).v;
+    let p = Person::from(&v);
+    assert_eq!(p.to_tuple(), (7, "c"));
+    assert!(round_trip::<Person>(&v));
+    assert_eq!(p.into_vec(), v);
   }
-  quote! { vec![#members] }.into() // be TokenStream
-}
 
-/// from attr to list of member and DataType
-fn list_members(mns: &Vec<Ident>, dts: &Vec<Ident>) -> TokenStream {
-  let mut members = quote! {};
-  for (i, n) in mns.iter().enumerate() {
-    let id = pre_ast_ident("", n, "", true);
-    let ast_id = syn::parse_macro_input!(id as syn::Ident); // be TokenStream
-    let dt = ast_dtype(&dts[i]);
-    members = quote! {
-      #members
-      ///
-      pub #ast_id: #dt,
-    };
+  /// field options rename skip default
+  #[derive(IterTuple)]
+  pub struct Account<'a> {
+    #[iter_tuple(rename = "user_id")]
+    pub id: u64,
+    #[iter_tuple(skip)]
+    pub cache: Vec<u64>,
+    #[iter_tuple(default = "none")]
+    pub name: &'a str
   }
-  members.into() // be TokenStream
-}
 
-/// from attr to tuple of Vec member name and Vec DataType
-fn parse_attr(attr: PM2TS) -> (Vec<Ident>, Vec<Ident>) {
-  let (mut mns, mut dts) = (Vec::<Ident>::new(), Vec::<Ident>::new());
-  let mut i = 0usize;
-  for tt in attr { // not use .into_iter().enumerate() to count skip Punct ','
-    match tt {
-    TokenTree::Group(gp) => { // match only Group
-      for t in gp.stream() {
-        match t {
-        TokenTree::Ident(dt) => { // match only Ident
-//          println!("{}: {:?}", n, dt);
-          if i == 0 { mns.push(dt); } else { dts.push(dt); }
-        },
-        _ => {} // skip Punct ',' etc
-        }
-      }
-      i += 1;
-    },
-    _ => {} // skip Punct ',' etc
+  /// [-- --nocapture] [-- --show-output]
+  #[test]
+  fn test_field_options() {
+    assert_eq!(Account::names(), ["user_id", "name"]);
+    let v = vec![to_any!(9, DataType::UInt64)];
+    let a = Account::from(&v);
+    assert_eq!((a.id, a.cache.len(), a.name), (9, 0, "none"));
+    #[cfg(feature = "sqlite")]
+    {
+      assert_eq!(a.to_sqlite3_vec()[0].0, ":user_id");
+      let c = sqlite::open(":memory:").unwrap();
+      let mut s = c.prepare("select 3 as user_id;").unwrap();
+      let row = s.iter().next().unwrap().unwrap();
+      assert_eq!(Account::from(&row).name, "none"); // absent column
+      let mut s = c.prepare("select 3 as user_id, null as name;").unwrap();
+      let row = s.iter().next().unwrap().unwrap();
+      assert!(std::panic::catch_unwind(|| Account::from(&row).id).is_err()); // NULL
     }
   }
-  (mns, dts)
-}
 
-/// check type is tuple and elem length (pipeline for TokenStream)
-fn tuple_check(item: TokenStream, n: usize, f: &str) -> TokenStream {
-  let ast = syn::parse_macro_input!(item as syn::ItemType);
-//  dbg!(ast.clone());
-  let ty = &ast.ty; // syn::Type::Tuple (syn::ItemType -> ty: Box<syn::Type>)
-//  println!("{:?}", ty);
-  let elem_len = match ty.deref() {
-  syn::Type::Tuple(typetuple) => { // syn::TypeTuple != syn::Type::Tuple
-//    println!("{:?}", typetuple);
-    typetuple.elems.len()
-  },
-  _ => { panic!("{} requires type alias of tuple", f); }
-  };
-//  println!("{}", elem_len);
-  if elem_len != n { panic!("{} attributes not match with tuple", f); }
-  ast.into_token_stream().into()
-}
-
-/// struct_derive
-/// - (optional)
-#[proc_macro_attribute]
-pub fn struct_derive(attr: TokenStream, item: TokenStream) -> TokenStream {
-//  println!("{:?}", attr);
-  let (mns, dts) = parse_attr(attr.into());
-  let (m, n) = (mns.len(), dts.len());
-  if m != n { panic!("struct_derive attributes not same length"); }
-  let ast_type_members: PM2TS = type_members(&dts).into();
-//  dbg!(ast_type_members.clone());
-  let ast_str_members: PM2TS = str_members(&mns).into();
-//  dbg!(ast_str_members.clone());
-  let ast_list_members: PM2TS = list_members(&mns, &dts).into();
-//  dbg!(ast_list_members.clone());
-  let ast_to_tuple_members: PM2TS = to_tuple_members(&mns, &dts).into();
-//  dbg!(ast_to_tuple_members.clone());
-  let ast_from_tuple_members: PM2TS = from_tuple_members(&mns).into();
-//  dbg!(ast_from_tuple_members.clone());
-  let ast_to_sqlite3_vec: PM2TS = to_sqlite3_vec(&mns, &dts).into();
-//  dbg!(ast_to_sqlite3_vec.clone());
-  let ast_from_anyvalue_col: PM2TS = from_anyvalue_col(&dts).into();
-//  dbg!(ast_from_anyvalue_col.clone());
-
-  let tp = tuple_check(item, n, "struct_derive");
-  let ast = syn::parse_macro_input!(tp as syn::ItemType);
-
-  let tpl_id = &ast.ident;
-//  println!("{:?}", tpl_id);
-  let st_id = pre_ast_ident("St", tpl_id, "", true); // outside of macro call
-  let ast_st_id = syn::parse_macro_input!(st_id as syn::Ident);
-//  dbg!(ast_st_id.clone());
-  let fnc_id = pre_ast_ident("to_", tpl_id, "", false); // to lowercase
-  let ast_fnc_id = syn::parse_macro_input!(fnc_id as syn::Ident);
-//  dbg!(ast_fnc_id.clone());
-  let rec_id = pre_ast_ident("Rec", tpl_id, "", true); // outside of macro call
-  let ast_rec_id = syn::parse_macro_input!(rec_id as syn::Ident);
-//  dbg!(ast_rec_id.clone());
-
-  quote! {
-#ast
-///
-pub struct #ast_st_id<'a> {
-  #ast_list_members
-}
-///
-impl<'a> #ast_st_id<'a> {
-  ///
-  pub fn members() -> Vec<&'a str> {
-    #ast_str_members
+  /// inner struct of flatten
+  #[struct_derive((id, name), (UInt64, Utf8), derive(Clone))]
+  pub type Customer<'a> = (u64, &'a str);
+
+  /// flatten member by struct_derive (type of the member is the inner struct)
+  #[struct_derive((id, customer, qty), (UInt64, flatten, Int32), derive(Clone))]
+  pub type Order<'a> = (u64, StCustomer<'a>, i32);
+
+  /// flatten member by derive IterTuple
+  #[derive(IterTuple)]
+  #[cfg_attr(feature = "serde", iter_tuple(serde))]
+  pub struct Invoice<'a> {
+    pub no: u64,
+    #[iter_tuple(flatten, rename = "order")]
+    pub order: StOrder<'a>
   }
-  ///
-  pub fn types() -> Vec<DataType> {
-    #ast_type_members
+
+  /// [-- --nocapture] [-- --show-output]
+  #[test]
+  fn test_flatten() {
+    assert_eq!(StOrder::members(), ["id", "customer_id", "customer_name", "qty"]);
+    assert_eq!(Invoice::names()[4], "order_qty");
+    assert_eq!((StOrder::LEN, Invoice::__LEN), (4, 5));
+    let v = vec![to_any!(1, DataType::UInt64), to_any!(2, DataType::UInt64),
+      to_any!("c", DataType::Utf8), to_any!(3, DataType::Int32)];
+    let o = StOrder::from(&v);
+    assert_eq!((o.customer.id, o.customer.name, o.qty), (2, "c", 3));
+    let mut w = v.clone();
+    w[2] = __any_str_owned!("e".into()); // owned in the flattened struct
+    assert_eq!(StOrder::from_anyvalues(&w).customer.name, "e");
+    #[cfg(feature = "sqlite")]
+    assert_eq!(o.to_sqlite3_vec()[2].0, ":customer_name");
+    assert!(round_trip::<StOrder>(&v));
+    let mut w = vec![to_any!(0, DataType::UInt64)];
+    w.extend(o.into_vec());
+    let i = Invoice::from(&w);
+    assert_eq!((i.no, i.order.customer.name), (0, "c"));
+    #[cfg(feature = "sqlite")]
+    {
+      assert_eq!(i.to_sqlite3_vec()[3].0, ":order_customer_name");
+      let s = <Invoice as ToSqlValueVec<sqlite::Value>>::to_sql_vec(&i);
+      assert_eq!(s, i.to_sqlite3_vec());
+    }
+    assert!(round_trip::<Invoice>(&w));
   }
-  ///
-  pub fn #ast_fnc_id(&self) -> #tpl_id<'_> {
-    #ast_to_tuple_members
+
+  /// sqlite Value of each row of the mapping table (rusqlite in test-rusqlite)
+  #[cfg(feature = "sqlite")]
+  #[struct_derive((i, u, f, b, s), (Int32, UInt8, Float32, Boolean, Utf8))]
+  pub type Sv<'a> = (i32, u8, f32, bool, &'a str);
+
+  /// [-- --nocapture] [-- --show-output]
+  #[cfg(feature = "sqlite")]
+  #[test]
+  fn test_to_sql_vec() {
+    use sqlite::Value;
+    let st = StSv::from((-1, 2, 0.5, true, "a"));
+    assert_eq!(<StSv as ToSqlValueVec<Value>>::to_sql_vec(&st), vec![
+      (":i", Value::Integer(-1)), (":u", Value::Integer(2)), (":f", Value::Float(0.5)),
+      (":b", Value::String("T".into())), (":s", Value::String("a".into()))]);
+    let c = sqlite::open(":memory:").unwrap();
+    c.execute("create table t (i integer, u integer, f real, b text, s text);").unwrap();
+    let mut s = c.prepare("insert into t values (:i, :u, :f, :b, :s);").unwrap();
+    s.bind(&st.to_sqlite3_vec()[..]).unwrap();
+    s.next().unwrap();
+    let mut s = c.prepare("select * from t;").unwrap();
+    let row = s.iter().next().unwrap().unwrap();
+    let r = StSv::from(&row);
+    assert_eq!((r.i, r.u, r.f, r.b, r.s), (-1, 2, 0.5, true, "a"));
   }
-}
-///
-impl<'a> IntoAnyValueVec<'a> for #ast_st_id<'a> {
-  ///
-  fn into_vec(self) -> Vec<AnyValue<'a>> {
-//    #ast_rec_id::from(self.#ast_fnc_id()).v // can't reference to data owned
-    #ast_rec_id::from(#ast_to_tuple_members).v
+
+  /// [-- --nocapture] [-- --show-output]
+  #[test]
+  fn test_schema() {
+    assert_eq!(StTpl::schema_string(), "id:UInt64,string:Utf8");
+    assert_eq!(StTpl::SCHEMA_HASH,
+      __schema_hash(0xcbf29ce484222325, StTpl::schema_string().as_bytes()));
+    assert_eq!(StTpl::SCHEMA_HASH, RecTpl::SCHEMA_HASH); // names forwarded
+    assert_eq!(Invoice::schema_string(),
+      "no:UInt64,order:{id:UInt64,customer:{id:UInt64,name:Utf8},qty:Int32}");
+    const H: u64 = Invoice::SCHEMA_HASH;
+    assert_ne!(H, StOrder::SCHEMA_HASH);
   }
-}
-///
-impl<'a> ToSqlite3ValueVec for #ast_st_id<'a> {
-  ///
-  fn to_sqlite3_vec(&self) -> Vec<(&'_ str, sqlite::Value)> {
-    #ast_to_sqlite3_vec
+
+  /// select and collect through LazyFrame
+  #[cfg(feature = "lazy")]
+  #[struct_derive((id, string), (UInt64, Utf8), lazy)]
+  pub type Lz<'a> = (u64, &'a str);
+
+  /// [-- --nocapture] [-- --show-output]
+  #[cfg(feature = "lazy")]
+  #[test]
+  fn test_lazy() {
+    use polars::prelude::*; // df! needs NamedFrom (0.25)
+    let src = df!("string" => &["a", "b"], "x" => &[0i32, 1], "id" => &[1i64, 2]).unwrap();
+    assert_eq!(StLz::select_exprs().len(), 2);
+    let mut df = DataFrame::default();
+    let rows = StLz::collect_from(src.lazy(), &mut df).unwrap();
+    assert_eq!(rows.iter().map(|st| (st.id, st.string)).collect::<Vec<_>>(),
+      vec![(1, "a"), (2, "b")]);
+    assert_eq!((df.width(), df.height()), (2, 2));
   }
-}
-///
-impl<'a> From<#tpl_id<'a>> for #ast_st_id<'a> {
-  ///
-  fn from(t: #tpl_id<'a>) -> #ast_st_id<'_> {
-    #ast_st_id{#ast_from_tuple_members}
+
+  /// Binary column (Binary of polars 0.25 enabled by feature polars-0_25)
+  #[struct_derive((id, u), (UInt64, Binary))]
+  #[tuple_derive(UInt64, Binary)]
+  pub type Blob = (u64, Vec<u8>);
+
+  /// [-- --nocapture] [-- --show-output]
+  #[test]
+  fn test_binary() {
+    let v = RecBlob::from((1, vec![0, 255])).v;
+    assert_eq!(StBlob::from(&v).u, [0, 255]);
+    assert!(round_trip::<StBlob>(&v));
   }
-}
-///
-impl<'a> From<&'a sqlite::Row> for #ast_st_id<'a> {
-  ///
-  fn from(row: &'a sqlite::Row) -> #ast_st_id<'_> {
-    #ast_st_id::from(#ast_fnc_id(row))
+
+  /// Date Time Datetime columns (days, nanoseconds, microseconds)
+  #[struct_derive((d, t, ts), (Date, Time, Datetime))]
+  #[cfg_attr(feature = "sqlite", tuple_sqlite3(Date, Time, Datetime))]
+  #[tuple_derive(Date, Time, Datetime, derive(Debug, PartialEq, Eq, Hash))]
+  pub type Tm = (i32, i64, i64);
+
+  /// [-- --nocapture] [-- --show-output]
+  #[test]
+  fn test_temporal() {
+    let v = RecTm::from((19000, 3_600_000_000_000, 1_641_600_000_000_000)).v;
+    assert_eq!(v[2].dtype(), StTm::dtypes()[2]);
+    assert_eq!(from_any!(v[2], DataType::Datetime), 1_641_600_000_000_000);
+    assert_eq!(v[2], to_any!(1_641_600_000_000_000, DataType::Datetime));
+    let st = StTm::from(&v);
+    assert_eq!((st.d, st.t, st.ts), (19000, 3_600_000_000_000, 1_641_600_000_000_000));
+    assert!(round_trip::<StTm>(&v));
+    assert!(check_anyvalues(&v, StTm::dtypes()).is_ok());
+    #[cfg(feature = "sqlite")]
+    {
+      let c = sqlite::open(":memory:").unwrap();
+      c.execute("create table t (d integer, t integer, ts integer);").unwrap();
+      let mut s = c.prepare("insert into t values (:d, :t, :ts);").unwrap();
+      s.bind(&st.to_sqlite3_vec()[..]).unwrap();
+      s.next().unwrap();
+      let mut s = c.prepare("select * from t;").unwrap();
+      let row = s.iter().next().unwrap().unwrap();
+      let r = StTm::from(&row);
+      assert_eq!((r.d, r.t, r.ts), (st.d, st.t, st.ts));
+    }
   }
-}
-///
-impl<'a> From<&'a Vec<AnyValue<'a>>> for #ast_st_id<'a> {
-  ///
-  fn from(v: &'a Vec<AnyValue<'a>>) -> #ast_st_id<'_> {
-    #ast_st_id::from(#ast_from_anyvalue_col)
+
+  /// Binary column as base64 (option serde)
+  #[cfg(feature = "serde")]
+  #[struct_derive((id, u), (UInt64, Binary), serde)]
+  #[tuple_derive(UInt64, Binary, serde)]
+  pub type Bin = (u64, Vec<u8>);
+
+  /// RecX as sequence (option serde)
+  #[cfg(feature = "serde")]
+  #[tuple_derive(UInt64, Utf8, derive(Debug, PartialEq), serde)]
+  pub type Seq<'a> = (u64, &'a str);
+
+  /// opt-in impls of the features
+  #[cfg(any(feature = "serde", feature = "csv", feature = "arrow", feature = "parquet",
+    feature = "ndjson", feature = "codec"))]
+  #[derive(IterTuple)]
+  #[cfg_attr(feature = "serde", iter_tuple(serde))]
+  #[cfg_attr(feature = "csv", iter_tuple(csv))]
+  #[cfg_attr(feature = "arrow", iter_tuple(arrow))]
+  #[cfg_attr(feature = "parquet", iter_tuple(parquet))]
+  #[cfg_attr(feature = "ndjson", iter_tuple(ndjson))]
+  #[cfg_attr(feature = "codec", iter_tuple(codec))]
+  pub struct Item<'a> {
+    pub id: u64,
+    pub string: &'a str
   }
-}
-  }.into()
-/*
-  dbg!(ast.clone());
-  ast.into_token_stream().into()
-*/
-}
 
-/// tuple_sqlite3
-/// - (optional) see crate sqlite https://crates.io/crates/sqlite
-#[proc_macro_attribute]
-pub fn tuple_sqlite3(attr: TokenStream, item: TokenStream) -> TokenStream {
-//  println!("{:?}", attr);
-  let mut n = 0usize;
-  let ts_cols = sqlite3_cols(attr.into(), &mut n); // outside of macro call
-  let ast_cols = syn::parse_macro_input!(ts_cols as syn::Expr);
-//  dbg!(ast_cols.clone());
-
-  let tp = tuple_check(item, n, "tuple_sqlite3");
-  let ast = syn::parse_macro_input!(tp as syn::ItemType);
-
-  let tpl_id = &ast.ident;
-//  println!("{:?}", tpl_id);
-  let rec_id = pre_ast_ident("Rec", tpl_id, "", true); // outside of macro call
-  let ast_rec_id = syn::parse_macro_input!(rec_id as syn::Ident);
-//  dbg!(ast_rec_id.clone());
-  let fnc_id = pre_ast_ident("to_", tpl_id, "", false); // to lowercase
-  let ast_fnc_id = syn::parse_macro_input!(fnc_id as syn::Ident);
-//  dbg!(ast_fnc_id.clone());
-
-  quote! {
-#ast
-///
-pub fn #ast_fnc_id<'a>(row: &'a sqlite::Row) -> #tpl_id<'_> {
-  #ast_cols
-}
-///
-impl<'a> From<&'a sqlite::Row> for #ast_rec_id<'a> {
-  ///
-  fn from(row: &'a sqlite::Row) -> #ast_rec_id<'_> {
-    #ast_rec_id::from(#ast_fnc_id(row))
+  /// other schema of the features (id only)
+  #[cfg(any(feature = "arrow", feature = "parquet", feature = "codec"))]
+  #[derive(IterTuple)]
+  #[cfg_attr(feature = "arrow", iter_tuple(arrow))]
+  #[cfg_attr(feature = "parquet", iter_tuple(parquet))]
+  #[cfg_attr(feature = "codec", iter_tuple(codec))]
+  pub struct ItemId {
+    pub id: u64
   }
-}
-  }.into()
-/*
-  dbg!(ast.clone());
-  ast.into_token_stream().into()
-*/
-}
 
-/// tuple_derive
-/// - Utf8, UInt64, Int64, UInt32, Int32, Float64, Float32, Boolean, Binary, ...
-/// - see Enum polars::datatypes::DataType
-#[proc_macro_attribute]
-pub fn tuple_derive(attr: TokenStream, item: TokenStream) -> TokenStream {
-//  println!("{:?}", attr);
-  let type_cols = type_cols(attr.clone().into()); // outside of macro call
-  let ast_type_members = syn::parse_macro_input!(type_cols as syn::Expr);
-//  dbg!(ast_type_members.clone());
-
-  let mut n = 0usize;
-  let ts_cols = vec_cols(attr.into(), &mut n); // outside of macro call
-  let ast_cols = syn::parse_macro_input!(ts_cols as syn::Stmt);
-//  dbg!(ast_cols.clone());
-
-  let tp = tuple_check(item, n, "tuple_derive");
-  let ast = syn::parse_macro_input!(tp as syn::ItemType);
-
-  let tpl_id = &ast.ident;
-//  println!("{:?}", tpl_id);
-  let rec_id = pre_ast_ident("Rec", tpl_id, "", true); // outside of macro call
-  let ast_rec_id = syn::parse_macro_input!(rec_id as syn::Ident);
-//  dbg!(ast_rec_id.clone());
-
-  quote! {
-#ast
-///
-pub struct #ast_rec_id<'a> {
-  ///
-  pub v: Vec<AnyValue<'a>>
-}
-///
-impl<'a> IntoIterator for #ast_rec_id<'a> {
-  ///
-  type Item = AnyValue<'a>;
-  ///
-  type IntoIter = std::vec::IntoIter<Self::Item>;
-  //type IntoIter: Iterator<Item = Self::Item>;
-  ///
-  fn into_iter(self) -> Self::IntoIter {
-    self.v.into_iter()
+  /// [-- --nocapture] [-- --show-output]
+  #[cfg(feature = "serde")]
+  #[test]
+  fn test_serde() {
+    let j = serde_json::to_string(&StBin::from((1, vec![0, 255]))).unwrap();
+    assert_eq!(j, r#"{"id":1,"u":"AP8="}"#);
+    assert_eq!(serde_json::from_str::<StBin>(&j).unwrap().u, [0, 255]);
+    let it = Item::from((1, "a"));
+    let j = serde_json::to_string(&it).unwrap();
+    assert_eq!(j, r#"{"id":1,"string":"a"}"#);
+    let it: Item = serde_json::from_str(r#"{"string":"b","id":"2","x":0}"#).unwrap();
+    assert_eq!((it.id, it.string), (2, "b"));
+    assert!(serde_json::from_str::<Item>(r#"{"id":3}"#).is_err());
+    let r = RecSeq::from((4, "c"));
+    assert_eq!(serde_json::to_string(&r).unwrap(), r#"[4,"c"]"#);
+    assert_eq!(serde_json::from_str::<RecSeq>(r#"[4,"c"]"#).unwrap(), r);
+    assert!(serde_json::from_str::<RecSeq>(r#"[-1,"c"]"#).is_err());
+    let j = r#"[5,"a\"b\\\n"]"#;
+    let r: RecSeq = serde_json::from_str(j).unwrap();
+    assert_eq!(r.v[1], crate::__any_str_owned!("a\"b\\\n".into()));
+    assert_eq!(serde_json::to_string(&r).unwrap(), j);
+    let mut d = serde_json::Deserializer::from_reader(j.as_bytes()); // no borrow
+    assert_eq!(<RecSeq as serde::Deserialize>::deserialize(&mut d).unwrap(), r);
+    assert_eq!(StTpl::from(&r.v).string, "a\"b\\\n"); // owned read by reference
+    assert_eq!(RecTpl::from_anyvalues(&r.v).v, r.v);
+    let e = serde_json::from_str::<Item>(r#"{"id":5,"string":"a\"b"}"#).err();
+    assert!(e.unwrap().to_string().starts_with("escaped string"));
+    let i: Invoice = serde_json::from_str(r#"{"no":0,"order_id":1,
+      "order_customer_id":2,"order_customer_name":"d","order_qty":3}"#).unwrap();
+    assert_eq!((i.order.customer.name, i.order.qty), ("d", 3));
   }
-}
-///
-impl<'a> From<#tpl_id<'a>> for #ast_rec_id<'a> {
-  ///
-  fn from(t: #tpl_id<'a>) -> #ast_rec_id<'_> {
-    #ast_cols
-    #ast_rec_id{v}
+
+  /// [-- --nocapture] [-- --show-output]
+  #[cfg(feature = "csv")]
+  #[test]
+  fn test_csv() {
+    let mut w = Vec::<u8>::new();
+    Item::write_csv(&mut w, &[Item::from((1, "a,b")), Item::from((2, "c"))]).unwrap();
+    assert_eq!(String::from_utf8(w).unwrap(), "id,string\n1,\"a,b\"\n2,c\n");
+    let csv = "x,string,id\r\n\n0,\"say \"\"hi\"\"\nthere\",3\n0,d,-4\n";
+    let mut rd = crate::csv_any::CsvReader::new(csv.as_bytes());
+    let it = Item::read_csv(&mut rd).unwrap().unwrap();
+    assert_eq!((it.id, it.string), (3, "say \"hi\"\nthere"));
+    assert_eq!(Item::read_csv(&mut rd).unwrap().err().map(|e| (e.line, e.col)), Some((5, 3)));
+    assert!(Item::read_csv(&mut rd).is_none());
+    let v = vec![to_any!(3, DataType::UInt64), crate::__any_str_owned!("a\"b".into())];
+    let mut w = Vec::<u8>::new();
+    crate::csv_any::write_csv(&mut w, Item::names(), std::iter::once(v)).unwrap();
+    assert_eq!(String::from_utf8(w).unwrap(), "id,string\n3,\"a\"\"b\"\n");
+    let mut rd = crate::csv_any::CsvReader::new("id\n1\n".as_bytes());
+    assert_eq!(Item::read_csv(&mut rd).unwrap().err().map(|e| e.line), Some(1));
+    assert!(Item::read_csv(&mut rd).is_none());
   }
-}
-///
-impl<'a> #ast_rec_id<'a> {
-  ///
-  pub fn types() -> Vec<DataType> {
-    #ast_type_members
+
+  /// [-- --nocapture] [-- --show-output]
+  #[cfg(feature = "arrow")]
+  #[test]
+  fn test_arrow() {
+    let b = Item::to_record_batch(&[Item::from((1, "a")), Item::from((2, "b"))]).unwrap();
+    assert_eq!(b.schema(), Item::arrow_schema().unwrap());
+    let rows = Item::from_record_batch(&b).unwrap();
+    assert_eq!(rows.iter().map(|it| (it.id, it.string)).collect::<Vec<_>>(),
+      vec![(1, "a"), (2, "b")]);
+    let b = ItemId::to_record_batch(&[]).unwrap();
+    assert!(Item::from_record_batch(&b).is_err());
+    let v = vec![to_any!(3, DataType::UInt64), crate::__any_str_owned!("a\"b\n".into())];
+    let b = crate::arrow_any::to_record_batch(Item::names(), Item::dtypes(),
+      std::iter::once(v)).unwrap();
+    assert_eq!(Item::from_record_batch(&b).unwrap()[0].string, "a\"b\n");
   }
-  ///
-  pub fn into_iter(t: #tpl_id<'a>) -> std::vec::IntoIter<AnyValue<'_>> {
-    #ast_rec_id::from(t).into_iter()
+
+  /// [-- --nocapture] [-- --show-output]
+  #[cfg(feature = "parquet")]
+  #[test]
+  fn test_parquet() {
+    use crate::arrow::array::{ArrayRef, LargeStringArray, StringViewArray, UInt64Array};
+    let path = std::env::temp_dir().join(format!("iter_tuple_{}.parquet", std::process::id()));
+    Item::write_parquet(&path, &[Item::from((1, "a")), Item::from((2, "b"))], None).unwrap();
+    let mut rd = Item::open_parquet(&path).unwrap();
+    let rows = Item::read_parquet(&mut rd).unwrap().unwrap();
+    assert_eq!(rows.iter().map(|it| (it.id, it.string)).collect::<Vec<_>>(),
+      vec![(1, "a"), (2, "b")]);
+    assert!(Item::read_parquet(&mut rd).is_none());
+    let mut rd = ItemId::open_parquet(&path).unwrap();
+    assert_eq!(ItemId::read_parquet(&mut rd).unwrap().unwrap().len(), 2);
+    let b = crate::arrow::record_batch::RecordBatch::try_from_iter([
+      ("id", std::sync::Arc::new(UInt64Array::from(vec![4])) as ArrayRef),
+      ("string", std::sync::Arc::new(LargeStringArray::from(vec!["d"])) as ArrayRef)]).unwrap();
+    let f = std::fs::File::create(&path).unwrap();
+    let mut w = crate::parquet::arrow::ArrowWriter::try_new(f, b.schema(), None).unwrap();
+    w.write(&b).unwrap();
+    w.close().unwrap();
+    let mut rd = Item::open_parquet(&path).unwrap();
+    assert_eq!(Item::read_parquet(&mut rd).unwrap().unwrap()[0].string, "d");
+    let b = crate::arrow::record_batch::RecordBatch::try_from_iter([
+      ("id", std::sync::Arc::new(UInt64Array::from(vec![5])) as ArrayRef),
+      ("string", std::sync::Arc::new(StringViewArray::from(vec!["e"])) as ArrayRef)]).unwrap();
+    assert_eq!(Item::from_record_batch(&b).unwrap()[0].string, "e");
+    ItemId::write_parquet(&path, &[ItemId{id: 3}], None).unwrap();
+    assert!(Item::open_parquet(&path).is_err());
+    std::fs::remove_file(&path).unwrap();
   }
-}
-  }.into()
-/*
-  dbg!(ast.clone());
-  ast.into_token_stream().into()
-*/
-}
 
-/// tests
-#[cfg(test)]
-mod tests {
-//  use super::*;
+  /// [-- --nocapture] [-- --show-output]
+  #[cfg(feature = "ndjson")]
+  #[test]
+  fn test_ndjson() {
+    let mut w = Vec::<u8>::new();
+    Item::write_ndjson(&mut w, &[Item::from((1, "a")), Item::from((2, "b"))]).unwrap();
+    assert_eq!(String::from_utf8(w).unwrap(),
+      "{\"id\":1,\"string\":\"a\"}\n{\"id\":2,\"string\":\"b\"}\n");
+    let src = "{\"id\":3,\"string\":\"c\"}\n\n{\"id\":4}\n";
+    let mut rd = crate::ndjson_any::NdjsonReader::new(src.as_bytes());
+    let it = Item::read_ndjson(&mut rd).unwrap().unwrap();
+    assert_eq!((it.id, it.string), (3, "c"));
+    assert_eq!(Item::read_ndjson(&mut rd).unwrap().err().map(|e| e.line), Some(3));
+    assert!(Item::read_ndjson(&mut rd).is_none());
+    let src = "{\"id\":5,\"string\":\"a\\\"b\\\\\\n\"}\n";
+    let mut rd = crate::ndjson_any::NdjsonReader::new(src.as_bytes());
+    let it = Item::read_ndjson(&mut rd).unwrap().unwrap();
+    assert_eq!((it.id, it.string), (5, "a\"b\\\n"));
+    let mut w = Vec::<u8>::new();
+    Item::write_ndjson(&mut w, &[it]).unwrap();
+    assert_eq!(String::from_utf8(w).unwrap(), src);
+  }
 
   /// [-- --nocapture] [-- --show-output]
-  /// can't use a procedural macro from the same crate that defines it
+  #[cfg(feature = "codec")]
   #[test]
-  fn test_iter_tuple() {
-    assert_eq!(true, true);
+  fn test_codec() {
+    let mut b = vec![];
+    Item::from((1, "a")).encode(&mut b).unwrap();
+    assert_eq!(b.len(), 8 + 8 + 4 + 1);
+    let it = Item::decode(&b).unwrap();
+    assert_eq!((it.id, it.string), (1, "a"));
+    Item::from((2, "bc")).encode(&mut b).unwrap();
+    assert!(Item::decode(&b).is_err());
+    let rows = Item::decode_all(&b).collect::<Result<Vec<_>, _>>().unwrap();
+    assert_eq!(rows.iter().map(|it| it.id).collect::<Vec<_>>(), [1, 2]);
+    assert_ne!(ItemId::SCHEMA_HASH, Item::SCHEMA_HASH);
+    assert_eq!(ItemId::decode(&b[..8 + 8]).err().map(|e| e.pos), Some(0));
+    let v = [to_any!(3, DataType::UInt64), crate::__any_str_owned!("a\"b\n".into())];
+    let mut b = vec![];
+    crate::codec_any::encode(&v, Item::dtypes(), Item::SCHEMA_HASH, &mut b).unwrap();
+    assert_eq!(Item::decode(&b).unwrap().string, "a\"b\n");
+    let n = b.len();
+    let w = [to_any!(4, DataType::Int32), crate::__any_str!("c")];
+    assert!(crate::codec_any::encode(&w, Item::dtypes(), Item::SCHEMA_HASH, &mut b).is_err());
+    assert!(crate::codec_any::encode(&v[..1], Item::dtypes(), 0, &mut b).is_err());
+    assert_eq!(b.len(), n); // unchanged on error
   }
-}
\ No newline at end of file
+}
@@ -0,0 +1,111 @@
+//! newline-delimited JSON of AnyValue driven by DataType (feature ndjson)
+//!
+//! - each line is a map keyed by names (values encoded as serde_any)
+//! - read one line at a time (strings borrowed from the line of NdjsonReader,
+//!   escaped strings unescaped into NdjsonReader)
+
+use std::fmt;
+use std::io::{BufRead, Write};
+use serde::{Serialize, Serializer};
+use crate::polars::prelude::{AnyValue, DataType};
+use crate::serde_any::{serialize_map, deserialize_map};
+
+/// error of NDJSON with line (1 origin, 0 not at a line)
+#[derive(Debug, Clone, PartialEq)]
+pub struct NdjsonError {
+  /// line (1 origin, 0 not at a line)
+  pub line: usize,
+  /// message
+  pub msg: String
+}
+
+impl NdjsonError {
+  /// constructor
+  pub fn new(line: usize, msg: impl fmt::Display) -> Self {
+    NdjsonError{line, msg: msg.to_string()}
+  }
+}
+
+impl fmt::Display for NdjsonError {
+  /// fmt
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "ndjson line {}: {}", self.line, self.msg)
+  }
+}
+
+impl std::error::Error for NdjsonError {}
+
+/// values serialized as map keyed by names
+struct AnyMap<'r, 'a>(&'r [&'r str], &'r [AnyValue<'a>]);
+
+impl Serialize for AnyMap<'_, '_> {
+  /// serialize
+  fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+    serialize_map(self.0, self.1, s)
+  }
+}
+
+/// write rows as lines of map keyed by names
+pub fn write_ndjson<'v, W: Write, I: Iterator<Item = Vec<AnyValue<'v>>>>(mut w: W,
+  names: &[&str], rows: I) -> Result<(), NdjsonError> {
+  for (r, v) in rows.enumerate() {
+    serde_json::to_writer(&mut w, &AnyMap(names, &v))
+      .map_err(|e| NdjsonError::new(r + 1, e))?;
+    w.write_all(b"\n").map_err(|e| NdjsonError::new(r + 1, e))?;
+  }
+  w.flush().map_err(|e| NdjsonError::new(0, e))
+}
+
+/// reader of one line at a time (blank lines skipped)
+pub struct NdjsonReader<R> {
+  /// source
+  r: R,
+  /// current line
+  line: String,
+  /// escaped strings of current line (unescaped)
+  owned: Vec<String>,
+  /// number of current line
+  no: usize
+}
+
+impl<R: BufRead> NdjsonReader<R> {
+  /// constructor
+  pub fn new(r: R) -> Self {
+    NdjsonReader{r, line: String::new(), owned: vec![], no: 0}
+  }
+
+  /// number of the last line read
+  pub fn line_no(&self) -> usize { self.no }
+
+  /// values of next line (None at end, strings borrowed from the line or owned)
+  pub fn next_values(&mut self, names: &'static [&'static str],
+    dtypes: &'static [DataType]) -> Option<Result<Vec<AnyValue<'_>>, NdjsonError>> {
+    loop {
+      self.line.clear();
+      self.no += 1;
+      match self.r.read_line(&mut self.line) {
+      Ok(0) => return None,
+      Ok(_) if self.line.trim().is_empty() => continue,
+      Ok(_) => break,
+      Err(e) => return Some(Err(NdjsonError::new(self.no, e)))
+      }
+    }
+    let mut d = serde_json::Deserializer::from_str(&self.line);
+    let v = deserialize_map(&mut d, names, dtypes, true).and_then(|v| d.end().map(|_| v));
+    let v = match v {
+    Ok(v) => v,
+    Err(e) => return Some(Err(NdjsonError::new(self.no, e)))
+    };
+    self.owned.clear();
+    let v = v.into_iter().map(|a| match a { // Err: index of owned
+    crate::__any_str_owned!(s) => {
+      self.owned.push(s.to_string());
+      Err(self.owned.len() - 1)
+    },
+    a => Ok(a)
+    }).collect::<Vec<_>>();
+    let owned = &self.owned;
+    Some(Ok(v.into_iter().map(|a|
+      a.unwrap_or_else(|k| crate::__any_str!(owned[k].as_str()))).collect()))
+  }
+}
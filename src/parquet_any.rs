@@ -0,0 +1,61 @@
+//! parquet of AnyValue through arrow RecordBatch (feature parquet)
+//!
+//! - file schema is validated against names and dtypes before reading
+//! - only the columns of names are read (one RecordBatch at a time)
+
+use std::fs::File;
+use std::path::Path;
+use arrow::record_batch::RecordBatch;
+use parquet::arrow::{ArrowWriter, ProjectionMask};
+use parquet::arrow::arrow_reader::{ParquetRecordBatchReader, ParquetRecordBatchReaderBuilder};
+use parquet::errors::ParquetError;
+use parquet::file::properties::WriterProperties;
+use crate::polars::prelude::{AnyValue, DataType};
+use crate::arrow_any::{arrow_dtype, arrow_readable, arrow_schema, to_record_batch};
+
+/// write rows to path (row groups split by props)
+pub fn write_parquet<'v, P: AsRef<Path>, I: Iterator<Item = Vec<AnyValue<'v>>>>(
+  path: P, names: &[&str], dtypes: &[DataType], rows: I,
+  props: Option<WriterProperties>) -> Result<(), ParquetError> {
+  let b = to_record_batch(names, dtypes, rows)?;
+  let mut w = ArrowWriter::try_new(File::create(path)?, arrow_schema(names, dtypes)?, props)?;
+  w.write(&b)?;
+  w.close()?;
+  Ok(())
+}
+
+/// reader of one RecordBatch at a time (columns of names, batches of row groups)
+pub struct ParquetReader {
+  /// batches of the file
+  r: ParquetRecordBatchReader,
+  /// current batch
+  b: Option<RecordBatch>
+}
+
+impl ParquetReader {
+  /// open path (file schema checked by dtypes, Large Utf8 Binary read too)
+  pub fn open<P: AsRef<Path>>(path: P, names: &[&str], dtypes: &[DataType]) ->
+    Result<Self, ParquetError> {
+    let rb = ParquetRecordBatchReaderBuilder::try_new(File::open(path)?)?;
+    let schema = rb.schema().clone();
+    let ixs = names.iter().zip(dtypes).map(|(n, dt)| {
+      let (i, f) = schema.column_with_name(n).ok_or_else(||
+        ParquetError::General(format!("no column {}", n)))?;
+      match arrow_readable(f.data_type(), dt)? {
+      true => Ok(i),
+      false => Err(ParquetError::General(
+        format!("column {} is {:?} not {:?}", n, f.data_type(), arrow_dtype(dt)?)))
+      }
+    }).collect::<Result<Vec<_>, ParquetError>>()?;
+    let mask = ProjectionMask::roots(rb.parquet_schema(), ixs);
+    Ok(ParquetReader{r: rb.with_projection(mask).build()?, b: None})
+  }
+
+  /// next batch (None at end, replaces the previous batch)
+  pub fn next_batch(&mut self) -> Option<Result<&RecordBatch, ParquetError>> {
+    match self.r.next()? {
+    Ok(b) => Some(Ok(self.b.insert(b))),
+    Err(e) => Some(Err(e.into()))
+    }
+  }
+}
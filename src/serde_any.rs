@@ -0,0 +1,217 @@
+//! serde of AnyValue driven by DataType (feature serde)
+//!
+//! - StX is a map keyed by names() and RecX is a sequence
+//! - Binary is a base64 string, integers are read from number or string
+//! - strings are borrowed from the input (as serde &str), an escaped string
+//!   (or any string of a reader) is owned in RecX and an error in StX
+
+use std::fmt;
+use serde::{Serializer, Deserializer};
+use serde::ser::{Serialize, SerializeMap, SerializeSeq, Error as _};
+use serde::de::{self, DeserializeSeed, Visitor, MapAccess, SeqAccess, IgnoredAny};
+use base64::Engine;
+use crate::polars::prelude::{AnyValue, DataType};
+use crate::is_str;
+
+/// AnyValue serialized by variant (Binary as base64 string)
+pub struct AnySer<'r, 'a>(pub &'r AnyValue<'a>);
+
+impl Serialize for AnySer<'_, '_> {
+  /// serialize
+  fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+    let b64 = &base64::engine::general_purpose::STANDARD;
+    match self.0 {
+    AnyValue::Int64(i) => s.serialize_i64(*i),
+    AnyValue::Int32(i) => s.serialize_i32(*i),
+    AnyValue::Int16(i) => s.serialize_i16(*i),
+    AnyValue::Int8(i) => s.serialize_i8(*i),
+    AnyValue::UInt64(u) => s.serialize_u64(*u),
+    AnyValue::UInt32(u) => s.serialize_u32(*u),
+    AnyValue::UInt16(u) => s.serialize_u16(*u),
+    AnyValue::UInt8(u) => s.serialize_u8(*u),
+    AnyValue::Float64(f) => s.serialize_f64(*f),
+    AnyValue::Float32(f) => s.serialize_f32(*f),
+    AnyValue::Boolean(b) => s.serialize_bool(*b),
+    crate::__any_str!(t) => s.serialize_str(t),
+    crate::__any_str_owned!(t) => s.serialize_str(t),
+    AnyValue::Binary(u) => s.serialize_str(&b64.encode(u)),
+    AnyValue::BinaryOwned(u) => s.serialize_str(&b64.encode(u)),
+    AnyValue::Null => s.serialize_none(),
+    a => Err(S::Error::custom(format!("unsupported AnyValue {:?}", a)))
+    }
+  }
+}
+
+/// AnyValue deserialized by DataType
+/// - owned: transient (escaped) string as owned string variant (else error)
+pub struct AnySeed<'r>(pub &'r DataType, pub bool);
+
+impl<'de> DeserializeSeed<'de> for AnySeed<'_> {
+  /// AnyValue borrowing input
+  type Value = AnyValue<'de>;
+  /// deserialize
+  fn deserialize<D: Deserializer<'de>>(self, d: D) -> Result<Self::Value, D::Error> {
+    match self.0 {
+    DataType::Null => d.deserialize_ignored_any(IgnoredAny).map(|_| AnyValue::Null),
+    _ => d.deserialize_any(self)
+    }
+  }
+}
+
+impl AnySeed<'_> {
+  /// from integer (range checked) or float to AnyValue of DataType
+  fn num<E: de::Error>(&self, i: Option<i128>, f: f64) -> Result<AnyValue<'static>, E> {
+    let r = |e| E::custom(format!("{} out of range of {:?}", f, e));
+    let int = || i.ok_or_else(|| E::custom(format!("{} is not integer", f)));
+    Ok(match self.0 {
+    DataType::Int64 => AnyValue::Int64(int()?.try_into().map_err(|_| r(self.0))?),
+    DataType::Int32 => AnyValue::Int32(int()?.try_into().map_err(|_| r(self.0))?),
+    DataType::Int16 => AnyValue::Int16(int()?.try_into().map_err(|_| r(self.0))?),
+    DataType::Int8 => AnyValue::Int8(int()?.try_into().map_err(|_| r(self.0))?),
+    DataType::UInt64 => AnyValue::UInt64(int()?.try_into().map_err(|_| r(self.0))?),
+    DataType::UInt32 => AnyValue::UInt32(int()?.try_into().map_err(|_| r(self.0))?),
+    DataType::UInt16 => AnyValue::UInt16(int()?.try_into().map_err(|_| r(self.0))?),
+    DataType::UInt8 => AnyValue::UInt8(int()?.try_into().map_err(|_| r(self.0))?),
+    DataType::Float64 => AnyValue::Float64(f),
+    DataType::Float32 => AnyValue::Float32(f as f32),
+    dt => return Err(E::custom(format!("number for {:?}", dt)))
+    })
+  }
+}
+
+impl<'de> Visitor<'de> for AnySeed<'_> {
+  /// AnyValue borrowing input
+  type Value = AnyValue<'de>;
+  /// expecting
+  fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "value of {:?}", self.0)
+  }
+  /// visit bool
+  fn visit_bool<E: de::Error>(self, b: bool) -> Result<Self::Value, E> {
+    match self.0 {
+    DataType::Boolean => Ok(AnyValue::Boolean(b)),
+    dt => Err(E::custom(format!("bool for {:?}", dt)))
+    }
+  }
+  /// visit i64
+  fn visit_i64<E: de::Error>(self, i: i64) -> Result<Self::Value, E> {
+    self.num(Some(i as i128), i as f64)
+  }
+  /// visit u64
+  fn visit_u64<E: de::Error>(self, u: u64) -> Result<Self::Value, E> {
+    self.num(Some(u as i128), u as f64)
+  }
+  /// visit f64
+  fn visit_f64<E: de::Error>(self, f: f64) -> Result<Self::Value, E> {
+    self.num(None, f)
+  }
+  /// number as string or base64 of Binary (transient string is owned or error)
+  fn visit_str<E: de::Error>(self, s: &str) -> Result<Self::Value, E> {
+    let b64 = &base64::engine::general_purpose::STANDARD;
+    match self.0 {
+    DataType::Binary => b64.decode(s).map(AnyValue::BinaryOwned).map_err(E::custom),
+    DataType::Boolean => s.parse().map(AnyValue::Boolean).map_err(E::custom),
+    DataType::Float64 | DataType::Float32 =>
+      self.num(None, s.parse().map_err(E::custom)?),
+    dt if is_str(dt) => match self.1 {
+      true => Ok(crate::__any_str_owned!(s.into())),
+      false => Err(E::custom(format!(
+        "escaped string {:?} can't be borrowed (StX borrows &str, use RecX)", s)))
+    },
+    _ => {
+      let i: i128 = s.parse().map_err(E::custom)?;
+      self.num(Some(i), i as f64)
+    }
+    }
+  }
+  /// visit borrowed str
+  fn visit_borrowed_str<E: de::Error>(self, s: &'de str) -> Result<Self::Value, E> {
+    match is_str(self.0) {
+    true => Ok(crate::__any_str!(s)),
+    false => self.visit_str(s)
+    }
+  }
+  /// visit bytes
+  fn visit_bytes<E: de::Error>(self, u: &[u8]) -> Result<Self::Value, E> {
+    match self.0 {
+    DataType::Binary => Ok(AnyValue::BinaryOwned(u.to_vec())),
+    dt => Err(E::custom(format!("bytes for {:?}", dt)))
+    }
+  }
+  /// visit none
+  fn visit_none<E: de::Error>(self) -> Result<Self::Value, E> {
+    Ok(AnyValue::Null)
+  }
+  /// visit unit
+  fn visit_unit<E: de::Error>(self) -> Result<Self::Value, E> {
+    Ok(AnyValue::Null)
+  }
+}
+
+/// serialize values as map keyed by names
+pub fn serialize_map<S: Serializer>(names: &[&str], v: &[AnyValue<'_>], s: S) ->
+  Result<S::Ok, S::Error> {
+  let mut m = s.serialize_map(Some(v.len()))?;
+  for (n, a) in names.iter().zip(v) { m.serialize_entry(n, &AnySer(a))?; }
+  m.end()
+}
+
+/// serialize values as sequence
+pub fn serialize_seq<S: Serializer>(v: &[AnyValue<'_>], s: S) ->
+  Result<S::Ok, S::Error> {
+  let mut q = s.serialize_seq(Some(v.len()))?;
+  for a in v { q.serialize_element(&AnySer(a))?; }
+  q.end()
+}
+
+/// deserialize map keyed by names (unknown keys ignored, missing is error)
+/// - owned: escaped string as owned string variant (else error)
+pub fn deserialize_map<'de, D: Deserializer<'de>>(d: D,
+  names: &'static [&'static str], dtypes: &'static [DataType], owned: bool) ->
+  Result<Vec<AnyValue<'de>>, D::Error> {
+  /// visitor of map
+  struct V(&'static [&'static str], &'static [DataType], bool);
+  impl<'de> Visitor<'de> for V {
+    type Value = Vec<AnyValue<'de>>;
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+      write!(f, "map of {:?}", self.0)
+    }
+    fn visit_map<A: MapAccess<'de>>(self, mut m: A) -> Result<Self::Value, A::Error> {
+      let mut v: Vec<Option<AnyValue<'de>>> = vec![None; self.0.len()];
+      while let Some(k) = m.next_key::<String>()? {
+        match self.0.iter().position(|n| *n == k) {
+        Some(i) => { v[i] = Some(m.next_value_seed(AnySeed(&self.1[i], self.2))?); },
+        None => { m.next_value::<IgnoredAny>()?; }
+        }
+      }
+      v.into_iter().enumerate().map(|(i, a)|
+        a.ok_or_else(|| de::Error::missing_field(self.0[i]))).collect()
+    }
+  }
+  d.deserialize_map(V(names, dtypes, owned))
+}
+
+/// deserialize sequence of dtypes (length checked, escaped string is owned)
+pub fn deserialize_seq<'de, D: Deserializer<'de>>(d: D,
+  dtypes: &'static [DataType]) -> Result<Vec<AnyValue<'de>>, D::Error> {
+  /// visitor of sequence
+  struct V(&'static [DataType]);
+  impl<'de> Visitor<'de> for V {
+    type Value = Vec<AnyValue<'de>>;
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+      write!(f, "sequence of {} values", self.0.len())
+    }
+    fn visit_seq<A: SeqAccess<'de>>(self, mut q: A) -> Result<Self::Value, A::Error> {
+      let mut v = Vec::with_capacity(self.0.len());
+      for (i, dt) in self.0.iter().enumerate() {
+        v.push(q.next_element_seed(AnySeed(dt, true))?
+          .ok_or_else(|| de::Error::invalid_length(i, &self))?);
+      }
+      if q.next_element::<IgnoredAny>()?.is_some() {
+        return Err(de::Error::invalid_length(self.0.len() + 1, &self));
+      }
+      Ok(v)
+    }
+  }
+  d.deserialize_seq(V(dtypes))
+}
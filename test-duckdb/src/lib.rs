@@ -0,0 +1,95 @@
+//! tests of option backend = duckdb (without feature sqlite of iter-tuple)
+
+#[cfg(test)]
+mod tests {
+  use iter_tuple::{struct_derive, tuple_sqlite3, tuple_derive, IterTuple};
+  use iter_tuple::ToSqlValueVec;
+  use duckdb::Connection;
+  use iter_tuple::polars::prelude::AnyValue;
+  use duckdb::types::{Value, TimeUnit};
+
+  #[struct_derive((id, name, ok, bin), (UInt64, Utf8, Boolean, Binary), backend = duckdb)]
+  #[tuple_sqlite3(UInt64, Utf8, Boolean, Binary, backend = duckdb)]
+  #[tuple_derive(UInt64, Utf8, Boolean, Binary)]
+  pub type Tpl<'a> = (u64, &'a str, bool, Vec<u8>);
+
+  #[struct_derive((d, t, ts), (Date, Time, Datetime), backend = duckdb)]
+  pub type Tm = (i32, i64, i64);
+
+  #[derive(IterTuple, Debug)]
+  #[iter_tuple(backend = duckdb)]
+  pub struct Named<'a> {
+    pub name: &'a str,
+    pub ok: bool,
+    #[iter_tuple(default = 7)]
+    pub missing: i64
+  }
+
+  fn conn() -> Connection {
+    let c = Connection::open_in_memory().unwrap();
+    c.execute_batch("create table t (id ubigint, name text, ok boolean, bin blob);
+      create table tm (d date, t time, ts timestamp);").unwrap();
+    c
+  }
+
+  #[test]
+  fn test_duckdb() {
+    let c = conn();
+    let st = StTpl::from((1, "a", true, vec![0, 255]));
+    let v = st.to_sql_vec();
+    assert_eq!(v, vec![(":id", Value::UBigInt(1)), (":name", Value::Text("a".into())),
+      (":ok", Value::Boolean(true)), (":bin", Value::Blob(vec![0, 255]))]);
+    // positional params: values of the vec in column order
+    c.execute("insert into t values (?, ?, ?, ?)",
+      duckdb::params_from_iter(v.into_iter().map(|(_, x)| x))).unwrap();
+    let st = StTpl::from((2, "b", false, vec![]));
+    c.execute("insert into t values (?, ?, ?, ?)",
+      duckdb::params_from_iter(st.to_sql_vec().into_iter().map(|(_, x)| x))).unwrap();
+    let mut s = c.prepare("select * from t order by id").unwrap();
+    let mut rows = s.query([]).unwrap();
+    let row = rows.next().unwrap().unwrap();
+    assert_eq!(StTpl::try_from(row).unwrap().to_tpl(), (1, "a", true, vec![0, 255]));
+    assert_eq!(to_tpl(row).unwrap(), (1, "a", true, vec![0, 255]));
+    let n = Named::try_from(row).unwrap();
+    assert_eq!((n.name, n.ok, n.missing), ("a", true, 7));
+    let row = rows.next().unwrap().unwrap();
+    assert_eq!(RecTpl::try_from(row).unwrap().v, vec![AnyValue::UInt64(2),
+      iter_tuple::__any_str!("b"), AnyValue::Boolean(false), AnyValue::Binary(&[])]);
+    assert!(rows.next().unwrap().is_none());
+  }
+
+  #[test]
+  fn test_duckdb_temporal() {
+    let c = conn();
+    let st = StTm::from((19130, 3_600_000_000_000, 1_641_600_000_000_000));
+    let v = st.to_sql_vec();
+    assert_eq!(v[0], (":d", Value::Date32(19130)));
+    assert_eq!(v[2], (":ts", Value::Timestamp(TimeUnit::Microsecond, 1_641_600_000_000_000)));
+    c.execute("insert into tm values (?, ?, ?)",
+      duckdb::params_from_iter(v.into_iter().map(|(_, x)| x))).unwrap();
+    let mut s = c.prepare("select d, t, ts, d = date '2022-05-18' as ok from tm").unwrap();
+    let mut rows = s.query([]).unwrap();
+    let row = rows.next().unwrap().unwrap();
+    assert!(row.get::<_, bool>("ok").unwrap());
+    let r = StTm::try_from(row).unwrap();
+    assert_eq!((r.d, r.t, r.ts), (19130, 3_600_000_000_000, 1_641_600_000_000_000));
+  }
+
+  #[test]
+  fn test_duckdb_error() {
+    let c = conn();
+    let mut s = c.prepare("select 'x' as name, true as ok").unwrap();
+    let mut rows = s.query([]).unwrap();
+    let row = rows.next().unwrap().unwrap();
+    assert!(StTpl::try_from(row).is_err()); // no column id
+    assert_eq!(Named::try_from(row).unwrap().missing, 7);
+    let mut s = c.prepare("select 'x' as name, true as ok, null as missing").unwrap();
+    let mut rows = s.query([]).unwrap();
+    let row = rows.next().unwrap().unwrap();
+    assert!(Named::try_from(row).is_err()); // NULL is not absent
+    let mut s = c.prepare("select 1 as d, 2 as t, 3 as ts").unwrap();
+    let mut rows = s.query([]).unwrap();
+    let row = rows.next().unwrap().unwrap();
+    assert!(StTm::try_from(row).is_err()); // integers are not temporal
+  }
+}
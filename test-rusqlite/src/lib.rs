@@ -0,0 +1,72 @@
+//! tests of option backend = rusqlite (without feature sqlite of iter-tuple)
+
+#[cfg(test)]
+mod tests {
+  use iter_tuple::{struct_derive, tuple_sqlite3, tuple_derive, IterTuple};
+  use iter_tuple::ToSqlValueVec;
+  use rusqlite::{Connection, ToSql};
+  use iter_tuple::polars::prelude::AnyValue;
+  use rusqlite::types::Value;
+
+  #[struct_derive((id, name, ok, bin), (UInt64, Utf8, Boolean, Binary), backend = rusqlite)]
+  #[tuple_sqlite3(UInt64, Utf8, Boolean, Binary, backend = rusqlite)]
+  #[tuple_derive(UInt64, Utf8, Boolean, Binary)]
+  pub type Tpl<'a> = (u64, &'a str, bool, Vec<u8>);
+
+  #[derive(IterTuple, Debug)]
+  #[iter_tuple(backend = rusqlite)]
+  pub struct Named<'a> {
+    pub name: &'a str,
+    pub ok: bool,
+    #[iter_tuple(default = 7)]
+    pub missing: i64
+  }
+
+  fn conn() -> Connection {
+    let c = Connection::open_in_memory().unwrap();
+    c.execute_batch("create table t (id integer, name text, ok integer, bin blob);").unwrap();
+    c
+  }
+
+  #[test]
+  fn test_rusqlite() {
+    let c = conn();
+    let st = StTpl::from((1, "a", true, vec![0, 255]));
+    let v = st.to_sql_vec();
+    assert_eq!(v, vec![(":id", Value::Integer(1)), (":name", Value::Text("a".into())),
+      (":ok", Value::Integer(1)), (":bin", Value::Blob(vec![0, 255]))]);
+    // named params: (tag, &dyn ToSql) borrowed from the vec of to_sql_vec
+    let p: Vec<(&str, &dyn ToSql)> = v.iter().map(|(t, x)| (*t, x as &dyn ToSql)).collect();
+    c.execute("insert into t values (:id, :name, :ok, :bin)", &p[..]).unwrap();
+    // positional params: values of the vec in column order
+    let st = StTpl::from((2, "b", false, vec![]));
+    let v = st.to_sql_vec();
+    c.execute("insert into t values (?, ?, ?, ?)",
+      rusqlite::params_from_iter(v.into_iter().map(|(_, x)| x))).unwrap();
+    let mut s = c.prepare("select * from t order by id").unwrap();
+    let mut rows = s.query([]).unwrap();
+    let row = rows.next().unwrap().unwrap();
+    assert_eq!(StTpl::try_from(row).unwrap().to_tpl(), (1, "a", true, vec![0, 255]));
+    assert_eq!(to_tpl(row).unwrap(), (1, "a", true, vec![0, 255]));
+    let n = Named::try_from(row).unwrap();
+    assert_eq!((n.name, n.ok, n.missing), ("a", true, 7));
+    let row = rows.next().unwrap().unwrap();
+    assert_eq!(RecTpl::try_from(row).unwrap().v, vec![AnyValue::UInt64(2),
+      iter_tuple::__any_str!("b"), AnyValue::Boolean(false), AnyValue::Binary(&[])]);
+    assert!(rows.next().unwrap().is_none());
+  }
+
+  #[test]
+  fn test_rusqlite_error() {
+    let c = conn();
+    let mut s = c.prepare("select 'x' as name, 1 as ok").unwrap();
+    let mut rows = s.query([]).unwrap();
+    let row = rows.next().unwrap().unwrap();
+    assert!(StTpl::try_from(row).is_err()); // id is text, no column bin
+    assert_eq!(Named::try_from(row).unwrap().missing, 7);
+    let mut s = c.prepare("select 'x' as name, 1 as ok, null as missing").unwrap();
+    let mut rows = s.query([]).unwrap();
+    let row = rows.next().unwrap().unwrap();
+    assert!(Named::try_from(row).is_err()); // NULL is not absent
+  }
+}